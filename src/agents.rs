@@ -0,0 +1,230 @@
+use crate::*;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentConfig {
+    pub name: String,
+    pub public_key: String,
+    pub key_type: KeyType,
+    pub system_prompt: String,
+}
+
+/// Signature scheme of an agent key, which must match the curve of the key.
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    Clone,
+    Copy,
+    PartialEq,
+    Default,
+)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum KeyType {
+    #[default]
+    Ed25519,
+    Secp256k1,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Registers an additional agent that players can target in `request`.
+    /// The primary agent configured in `new` is always available.
+    pub fn register_agent(
+        &mut self,
+        name: String,
+        public_key: String,
+        key_type: Option<KeyType>,
+        system_prompt: String,
+    ) {
+        self.assert_owner();
+        require!(!name.is_empty(), "ERR_EMPTY_AGENT_NAME");
+        require!(name != self.agent_name, "ERR_PRIMARY_AGENT");
+        let key_type = key_type.unwrap_or_default();
+        require!(
+            is_valid_public_key(&public_key, key_type),
+            "ERR_INVALID_PUBLIC_KEY"
+        );
+
+        self.agents.insert(
+            &name,
+            &AgentConfig {
+                name: name.clone(),
+                public_key,
+                key_type,
+                system_prompt,
+            },
+        );
+    }
+
+    /// Refused while open or queued requests still target the agent, since
+    /// their responses are verified against its key.
+    pub fn unregister_agent(&mut self, name: String) {
+        self.assert_owner();
+        require!(
+            self.get_agent_request_count(name.clone()) == 0,
+            "ERR_AGENT_IN_USE"
+        );
+        self.agents.remove(&name).expect("ERR_UNKNOWN_AGENT");
+    }
+
+    /// Open and queued requests targeting the agent `name`.
+    pub fn get_agent_request_count(&self, name: String) -> u64 {
+        self.agent_requests.get(&name).unwrap_or(0)
+    }
+
+    pub fn get_agent(&self, name: String) -> Option<AgentConfig> {
+        if name == self.agent_name {
+            Some(self.primary_agent())
+        } else {
+            self.agents.get(&name)
+        }
+    }
+
+    pub fn get_champion_prompt(&self, word: String) -> Option<String> {
+        self.champion_prompts.get(&word.to_lowercase())
+    }
+
+    /// Registers the primary agent prompt used while `word` is champion, or
+    /// removes it with `None` so the default prompt applies again.
+    pub fn set_champion_prompt(&mut self, word: String, prompt: Option<String>) {
+        self.assert_owner();
+        let word = word.to_lowercase();
+        match prompt {
+            Some(prompt) => self.champion_prompts.insert(&word, &prompt),
+            None => self.champion_prompts.remove(&word),
+        };
+    }
+
+    /// Exact bytes the agent must sign to respond to `request_id` with `ok`
+    /// and `data`, i.e. what `respond` verifies against. The challenge is
+    /// filled in from the request.
+    pub fn agent_payload_bytes(
+        &self,
+        request_id: RequestId,
+        ok: bool,
+        data: Option<String>,
+    ) -> Vec<u8> {
+        let request = self.get_request(request_id);
+        signing_payload(
+            request_id,
+            &Response {
+                ok,
+                data,
+                signature: None,
+                challenge: Some(request.challenge),
+                originator: None,
+            },
+        )
+    }
+
+    /// The primary agent followed by every registered agent.
+    pub fn list_agents(&self) -> Vec<AgentConfig> {
+        let mut agents = vec![self.primary_agent()];
+        agents.extend(self.agents.values());
+        agents
+    }
+}
+
+impl Contract {
+    pub(crate) fn primary_agent(&self) -> AgentConfig {
+        AgentConfig {
+            name: self.agent_name.clone(),
+            public_key: self.agent_public_key.clone(),
+            key_type: self.agent_key_type,
+            system_prompt: self.agent_system_prompt.clone(),
+        }
+    }
+
+    pub(crate) fn agent_config(&self, name: &str) -> AgentConfig {
+        if name == self.agent_name {
+            self.primary_agent()
+        } else {
+            self.agents
+                .get(&name.to_string())
+                .unwrap_or_else(|| env::panic_str("ERR_UNKNOWN_AGENT"))
+        }
+    }
+
+    /// Prompt for judging `request`: registered agents always use their own.
+    /// The primary agent switches to the prompt of the challenged champion
+    /// when one is registered, then to the assembled prompt segments.
+    pub(crate) fn request_prompt(&self, request: &Request) -> String {
+        if request.agent == self.agent_name {
+            if let Some(prompt) = self.champion_prompts.get(&request.champion_at_request) {
+                return prompt;
+            }
+            if let Some(prompt) = self.assemble_prompt(&request.champion_at_request) {
+                return prompt;
+            }
+        }
+        self.agent_config(&request.agent).system_prompt
+    }
+
+    /// Counts a new open or queued request targeting `agent`.
+    pub(crate) fn add_agent_request(&mut self, agent: &String) {
+        let count = self.agent_requests.get(agent).unwrap_or(0);
+        self.agent_requests.insert(agent, &(count + 1));
+    }
+
+    /// Counterpart of `add_agent_request` once the request no longer waits
+    /// on `agent`.
+    pub(crate) fn remove_agent_request(&mut self, agent: &String) {
+        match self.agent_requests.get(agent) {
+            Some(count) if count > 1 => {
+                self.agent_requests.insert(agent, &(count - 1));
+            }
+            _ => {
+                self.agent_requests.remove(agent);
+            }
+        }
+    }
+
+    /// Checks the response signature against the key of the agent the request
+    /// targeted, `false` if that agent is gone.
+    pub(crate) fn is_signed_by_agent(&self, request_id: RequestId, response: &Response) -> bool {
+        let request = self.get_request(request_id);
+        let Some(agent) = self.get_agent(request.agent) else {
+            return false;
+        };
+
+        match &response.signature {
+            Some(signature) => verify_signature(
+                &agent.public_key,
+                agent.key_type,
+                &signing_payload(request_id, response),
+                signature,
+            ),
+            None => false,
+        }
+    }
+}
+
+/// Bytes the agent signs: borsh encoding of `(request_id, ok, data, challenge)`
+/// with `challenge` as an `Option`, or of `(request_id, ok, data)` for a
+/// response without one, as agents that predate challenges sign.
+pub(crate) fn signing_payload(request_id: RequestId, response: &Response) -> Vec<u8> {
+    match &response.challenge {
+        Some(_) => {
+            near_sdk::borsh::to_vec(&(request_id, response.ok, &response.data, &response.challenge))
+        }
+        None => near_sdk::borsh::to_vec(&(request_id, response.ok, &response.data)),
+    }
+    .expect("Failed to serialize signing payload")
+}
+
+/// Bytes the agent signs to crown a champion through `crown_champion_verified`.
+pub(crate) fn crown_signing_payload(
+    word: &str,
+    owner: &AccountId,
+    reason: &str,
+    current_champion: &str,
+    nonce: u64,
+) -> Vec<u8> {
+    near_sdk::borsh::to_vec(&(word, owner, reason, current_champion, nonce))
+        .expect("Failed to serialize signing payload")
+}
@@ -0,0 +1,167 @@
+use crate::*;
+
+#[derive(
+    BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone, PartialEq,
+)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum Outcome {
+    Won,
+    Lost,
+    /// Timed out, cancelled, or the agent ruling could not be applied.
+    Unresolved,
+    /// Ruled a win against a champion another win had already dethroned, or
+    /// lost the `tie_break` to such a win.
+    Superseded,
+}
+
+/// Audit log entry written whenever a request is resolved.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct StoredResponse {
+    pub request_id: RequestId,
+    #[schemars(with = "String")]
+    pub originator_id: AccountId,
+    pub message: String,
+    pub champion_at_request: String,
+    pub outcome: Outcome,
+    pub reason: Option<String>,
+    pub block_height: BlockHeight,
+    /// Agent signature of the response, `None` for requests the contract
+    /// closed itself.
+    pub signature: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccountEvent {
+    pub request_id: RequestId,
+    pub message: String,
+    pub champion_at_request: String,
+    pub outcome: Outcome,
+    pub block_height: BlockHeight,
+}
+
+/// One chunk of `get_responses_chunk`.
+#[derive(Serialize, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ResponsesChunk {
+    pub items: Vec<StoredResponse>,
+    /// Cursor for the next chunk, `None` once the log is exhausted.
+    pub next: Option<String>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Resolved requests of `account_id`, oldest first.
+    pub fn get_account_history(
+        &self,
+        account_id: AccountId,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<AccountEvent> {
+        let log_indices = match self.account_log.get(&account_id) {
+            Some(log_indices) => log_indices,
+            None => return vec![],
+        };
+        let from_index = from_index.unwrap_or(0);
+        let limit = page_limit(limit);
+
+        (from_index..std::cmp::min(from_index.saturating_add(limit), log_indices.len()))
+            .filter_map(|index| log_indices.get(index))
+            .filter_map(|log_index| self.responses_log.get(log_index))
+            .map(|entry| AccountEvent {
+                request_id: entry.request_id,
+                message: entry.message,
+                champion_at_request: entry.champion_at_request,
+                outcome: entry.outcome,
+                block_height: entry.block_height,
+            })
+            .collect()
+    }
+
+    /// Every resolved request in resolution order, for indexers.
+    pub fn get_responses_log(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<StoredResponse> {
+        let from_index = from_index.unwrap_or(0);
+        let limit = page_limit(limit);
+
+        (from_index..std::cmp::min(from_index.saturating_add(limit), self.responses_log.len()))
+            .filter_map(|index| self.responses_log.get(index))
+            .collect()
+    }
+
+    /// Agent signature over the response that resolved `request_id`, i.e.
+    /// over the borsh `(request_id, ok, data, challenge)` of the `respond`
+    /// call, or `(request_id, ok, data)` when it echoed no challenge, so it
+    /// can be checked against the agent key independently.
+    pub fn get_response_signature(&self, request_id: RequestId) -> Option<String> {
+        self.log_indices
+            .get(&request_id)
+            .and_then(|log_index| self.responses_log.get(log_index))
+            .and_then(|entry| entry.signature)
+    }
+
+    /// Streams `get_responses_log` in fixed `DEFAULT_PAGE_LIMIT` chunks.
+    /// Start without a cursor and pass back `next` until it is `None`. The
+    /// cursor is the decimal index of the next entry; the log is append-only,
+    /// so cursors stay valid while new entries land.
+    pub fn get_responses_chunk(&self, cursor: Option<String>) -> ResponsesChunk {
+        let from_index: u64 = cursor.map_or(0, |cursor| cursor.parse().expect("ERR_BAD_CURSOR"));
+        let to_index = std::cmp::min(
+            from_index.saturating_add(DEFAULT_PAGE_LIMIT),
+            self.responses_log.len(),
+        );
+
+        ResponsesChunk {
+            items: (from_index..to_index)
+                .filter_map(|index| self.responses_log.get(index))
+                .collect(),
+            next: (to_index < self.responses_log.len()).then(|| to_index.to_string()),
+        }
+    }
+}
+
+impl Contract {
+    pub(crate) fn log_resolution(
+        &mut self,
+        request_id: RequestId,
+        request: &Request,
+        outcome: Outcome,
+        reason: Option<String>,
+        signature: Option<String>,
+    ) {
+        self.update_timeline(request_id, |timeline| {
+            timeline.resolved = Some(env::block_height())
+        });
+
+        let log_index = self.responses_log.len();
+        self.responses_log.push(&StoredResponse {
+            request_id,
+            originator_id: request.originator_id.clone(),
+            message: request.message.clone(),
+            champion_at_request: request.champion_at_request.clone(),
+            outcome,
+            reason,
+            block_height: env::block_height(),
+            signature,
+        });
+        self.log_indices.insert(&request_id, &log_index);
+
+        let mut log_indices = self
+            .account_log
+            .get(&request.originator_id)
+            .unwrap_or_else(|| {
+                Vector::new(StorageKey::AccountLogInner {
+                    account_hash: env::sha256_array(request.originator_id.as_bytes()),
+                })
+            });
+        log_indices.push(&log_index);
+        self.account_log
+            .insert(&request.originator_id, &log_indices);
+    }
+}
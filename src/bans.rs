@@ -0,0 +1,42 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    pub fn is_banned(&self, account_id: AccountId) -> bool {
+        self.banned_accounts.contains(&account_id)
+    }
+
+    /// Banned accounts in ban order.
+    pub fn get_banned_accounts(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<AccountId> {
+        let banned_accounts = self.banned_accounts.as_vector();
+        let from_index = from_index.unwrap_or(0);
+
+        (from_index
+            ..std::cmp::min(
+                from_index.saturating_add(page_limit(limit)),
+                banned_accounts.len(),
+            ))
+            .filter_map(|index| banned_accounts.get(index))
+            .collect()
+    }
+
+    /// Rejects further requests from `account_id`. Its open requests are left
+    /// to resolve normally.
+    pub fn ban_account(&mut self, account_id: AccountId) {
+        self.assert_owner_or_operator();
+        if self.banned_accounts.insert(&account_id) {
+            log!("Banned {}", account_id);
+        }
+    }
+
+    pub fn unban_account(&mut self, account_id: AccountId) {
+        self.assert_owner_or_operator();
+        if self.banned_accounts.remove(&account_id) {
+            log!("Unbanned {}", account_id);
+        }
+    }
+}
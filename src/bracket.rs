@@ -0,0 +1,157 @@
+use crate::*;
+
+/// Most words a bracket may be seeded with.
+const MAX_BRACKET_WORDS: usize = 64;
+
+/// Single elimination tournament judged by the primary agent. Within a round,
+/// words `2i` and `2i + 1` meet and an odd last word gets a bye.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct Bracket {
+    /// Seeded words first, each later round holds the winners of the one
+    /// before it. The last round fills up as matches are decided.
+    pub rounds: Vec<Vec<String>>,
+    /// A match request is open, the next one waits for its ruling.
+    pub match_in_play: bool,
+}
+
+/// Ties a request to a bracket match. The request message is the challenger.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct BracketMatch {
+    pub round: u32,
+    pub index: u32,
+    pub defender: String,
+}
+
+impl Bracket {
+    /// Only set once the final, a round of exactly two words, has been
+    /// played. A single word in the last round is not enough on its own,
+    /// it may just be the first winner of a larger round.
+    pub fn winner(&self) -> Option<&String> {
+        match self.rounds.as_slice() {
+            [.., last_played, last] if last_played.len() == 2 && last.len() == 1 => last.first(),
+            _ => None,
+        }
+    }
+
+    /// Next match to play as `(match, challenger)`, advancing byes and
+    /// opening new rounds on the way. `None` once the bracket has a winner.
+    fn next_match(&mut self) -> Option<(BracketMatch, String)> {
+        loop {
+            if self.winner().is_some() {
+                return None;
+            }
+            let round = self.rounds.len() - 2;
+            let index = self.rounds[round + 1].len();
+            let playing = &self.rounds[round];
+
+            if 2 * index >= playing.len() {
+                self.rounds.push(vec![]);
+            } else if 2 * index + 1 == playing.len() {
+                let bye = playing[2 * index].clone();
+                self.rounds[round + 1].push(bye);
+            } else {
+                return Some((
+                    BracketMatch {
+                        round: round as u32,
+                        index: index as u32,
+                        defender: playing[2 * index].clone(),
+                    },
+                    playing[2 * index + 1].clone(),
+                ));
+            }
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_bracket(&self) -> Option<Bracket> {
+        self.bracket.clone()
+    }
+
+    /// Seeds a new bracket, replacing a finished one. Runs next to the
+    /// regular game without touching the champion.
+    pub fn create_bracket(&mut self, words: Vec<String>) {
+        self.assert_owner();
+        require!(
+            self.bracket
+                .as_ref()
+                .is_none_or(|bracket| bracket.winner().is_some()),
+            "ERR_BRACKET_IN_PROGRESS"
+        );
+        require!(
+            (2..=MAX_BRACKET_WORDS).contains(&words.len()),
+            "ERR_BAD_BRACKET_SIZE"
+        );
+
+        let words: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+        for word in &words {
+            assert!(is_valid_string(word.as_str()), "Illegal input string");
+        }
+
+        self.bracket = Some(Bracket {
+            rounds: vec![words, vec![]],
+            match_in_play: false,
+        });
+    }
+
+    /// Sends the next bracket match to the primary agent through the regular
+    /// request flow, the challenger being judged against the defender.
+    pub fn play_bracket_match(&mut self) {
+        self.assert_operator();
+        require!(
+            remaining_gas() >= self.request_gas_floor(),
+            "Not enough remaining gas to make the request"
+        );
+
+        let mut bracket = self.bracket.clone().expect("ERR_NO_BRACKET");
+        require!(!bracket.match_in_play, "ERR_MATCH_IN_PLAY");
+        let (bracket_match, challenger) = bracket.next_match().expect("ERR_BRACKET_FINISHED");
+        bracket.match_in_play = true;
+        self.bracket = Some(bracket);
+
+        let yield_promise = self.create_request(NewRequest {
+            originator_id: env::predecessor_account_id(),
+            message: challenger,
+            agent: self.agent_name.clone(),
+            provenance: None,
+            locale: None,
+            tags: vec![],
+            deposit: NearToken::from_yoctonear(0),
+            bracket_match: Some(bracket_match),
+        });
+
+        env::promise_return(yield_promise);
+    }
+}
+
+impl Contract {
+    /// Frees the bracket for the next match, called whenever a match request
+    /// is removed. An unresolved match is simply played again.
+    pub(crate) fn release_bracket_match(&mut self) {
+        if let Some(bracket) = self.bracket.as_mut() {
+            bracket.match_in_play = false;
+        }
+    }
+
+    pub(crate) fn advance_bracket(&mut self, bracket_match: &BracketMatch, winner: String) {
+        let Some(bracket) = self.bracket.as_mut() else {
+            return;
+        };
+        let next_round = bracket_match.round as usize + 1;
+        if bracket.rounds.get(next_round).map(|round| round.len())
+            != Some(bracket_match.index as usize)
+        {
+            return;
+        }
+
+        bracket.rounds[next_round].push(winner);
+        if let Some(bracket_winner) = bracket.winner() {
+            log!("Bracket won by {}", bracket_winner);
+        }
+    }
+}
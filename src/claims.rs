@@ -0,0 +1,63 @@
+use crate::*;
+
+pub(crate) const DEFAULT_CLAIM_LEASE_BLOCKS: u64 = 50;
+/// Bounds how many request ids one `claim_next_request` call inspects.
+const MAX_CLAIM_SCAN: u64 = 50;
+
+#[near_bindgen]
+impl Contract {
+    /// Hands the oldest open request that is neither being resolved nor under
+    /// a live lease to the calling worker and leases it for
+    /// `claim_lease_blocks`. An expired lease makes the request claimable again.
+    pub fn claim_next_request(&mut self) -> Option<(RequestId, Request)> {
+        self.assert_operator();
+
+        let mut request_id = self.oldest_open_request;
+        let scan_end = std::cmp::min(
+            self.num_requests,
+            self.oldest_open_request.saturating_add(MAX_CLAIM_SCAN),
+        );
+
+        while request_id < scan_end {
+            if let Some(request) = self.requests.get(&request_id) {
+                let leased = self.claims.get(&request_id).is_some_and(|claimed_block| {
+                    env::block_height() < claimed_block + self.claim_lease_blocks
+                });
+
+                if !leased && !self.responses.contains_key(&request_id) {
+                    self.claims.insert(&request_id, &env::block_height());
+                    return Some((request_id, request));
+                }
+            }
+            request_id += 1;
+        }
+
+        None
+    }
+
+    /// Marks a request as picked up by the agent, required before `respond`
+    /// when the `require_ack` flag is on.
+    pub fn ack_request(&mut self, request_id: RequestId) {
+        require!(request_id < self.num_requests, "ERR_BAD_REQUEST_ID");
+        self.assert_operator();
+
+        let mut request = self
+            .requests
+            .get(&request_id)
+            .expect("Request ID not found");
+        request.acked = true;
+        self.requests.insert(&request_id, &request);
+        self.update_timeline(request_id, |timeline| {
+            timeline.acked = Some(env::block_height())
+        });
+    }
+
+    pub fn get_claim_lease_blocks(&self) -> u64 {
+        self.claim_lease_blocks
+    }
+
+    pub fn set_claim_lease_blocks(&mut self, blocks: u64) {
+        self.assert_owner();
+        self.claim_lease_blocks = blocks;
+    }
+}
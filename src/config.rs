@@ -0,0 +1,291 @@
+use crate::*;
+// The JsonSchema derive refers to a bare `serde` path for `serde(with)` fields.
+use near_sdk::serde;
+use near_sdk::serde_with::rust::double_option;
+
+/// Partial configuration update. Only the fields present are applied. For
+/// nullable settings, an explicit `null` clears the value while an absent
+/// field leaves it unchanged.
+#[derive(Deserialize, JsonSchema, Default)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize))]
+#[serde(crate = "near_sdk::serde", default)]
+pub struct UpdateConfig {
+    pub system_prompt: Option<String>,
+    pub min_blocks_between_prompt_changes: Option<u64>,
+    pub maintenance: Option<bool>,
+    pub feature_flags: Option<FeatureFlags>,
+    pub response_deadline_blocks: Option<u64>,
+    pub timeout_response: Option<Response>,
+    pub min_champion_len: Option<u32>,
+    pub min_champions_to_start: Option<u64>,
+    pub crowned_cooldown_blocks: Option<u64>,
+    pub repeat_guess_window: Option<u64>,
+    pub claim_lease_blocks: Option<u64>,
+    pub tie_break: Option<TieBreak>,
+    pub max_bonus_points: Option<u64>,
+    #[schemars(with = "Option<String>")]
+    pub min_prepaid_gas: Option<Gas>,
+    #[schemars(with = "Option<String>")]
+    pub yield_callback_gas: Option<Gas>,
+    #[serde(with = "double_option")]
+    #[schemars(with = "Option<Option<u64>>")]
+    pub max_open_requests: Option<Option<u64>>,
+    pub max_queue_length: Option<u64>,
+    #[serde(with = "double_option")]
+    #[schemars(with = "Option<Option<u64>>")]
+    pub decay_blocks: Option<Option<u64>>,
+    #[serde(with = "double_option")]
+    #[schemars(with = "Option<Option<u64>>")]
+    pub max_consecutive_agent_errors: Option<Option<u64>>,
+    #[serde(with = "double_option")]
+    #[schemars(with = "Option<Option<String>>")]
+    pub target_champion: Option<Option<String>>,
+    #[serde(with = "double_option")]
+    #[schemars(with = "Option<Option<String>>")]
+    pub question_template: Option<Option<String>>,
+    #[serde(with = "double_option")]
+    #[schemars(with = "Option<Option<String>>")]
+    pub event_forwarder: Option<Option<AccountId>>,
+    #[serde(with = "double_option")]
+    #[schemars(with = "Option<Option<String>>")]
+    pub trophy_nft_contract: Option<Option<AccountId>>,
+    #[schemars(with = "Option<String>")]
+    pub trophy_mint_deposit: Option<NearToken>,
+    #[schemars(with = "Option<String>")]
+    pub min_deposit: Option<NearToken>,
+    #[serde(with = "double_option")]
+    #[schemars(with = "Option<Option<String>>")]
+    pub max_total_deposit: Option<Option<NearToken>>,
+    pub win_fee_bps: Option<u32>,
+    #[schemars(with = "Option<String>")]
+    pub immunity_price_per_block: Option<NearToken>,
+    pub max_immunity_blocks: Option<u64>,
+    #[schemars(with = "Option<String>")]
+    pub win_reward_base: Option<NearToken>,
+    #[schemars(with = "Option<String>")]
+    pub win_reward_per_block: Option<NearToken>,
+    #[schemars(with = "Option<String>")]
+    pub keeper_reward: Option<NearToken>,
+    pub dominance_weights: Option<DominanceWeights>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Base58 sha256 over the borsh encoding of every setting, agents and
+    /// roles included, to compare deployments. Keep in sync with `UpdateConfig`.
+    pub fn get_config_hash(&self) -> String {
+        let prompt_segments: Vec<Option<String>> = PROMPT_SEGMENTS
+            .iter()
+            .map(|segment| self.prompt_segments.get(segment))
+            .collect();
+        let agents = (
+            self.list_agents(),
+            prompt_segments,
+            &self.question_template,
+            &self.owner_id,
+            &self.operator_id,
+        );
+        let limits = (
+            self.min_blocks_between_prompt_changes,
+            self.response_deadline_blocks,
+            self.min_prepaid_gas,
+            self.yield_callback_gas,
+            self.min_champion_len,
+            self.min_champions_to_start,
+            self.crowned_cooldown_blocks,
+            self.max_open_requests,
+            self.max_queue_length,
+            self.claim_lease_blocks,
+            self.max_consecutive_agent_errors,
+            self.decay_blocks,
+            self.repeat_guess_window,
+            self.max_bonus_points,
+        );
+        let rules = (
+            self.maintenance,
+            &self.feature_flags,
+            &self.timeout_response,
+            &self.target_champion,
+            &self.dominance_weights,
+            self.tie_break,
+        );
+        let integrations = (
+            &self.event_forwarder,
+            &self.trophy_nft_contract,
+            self.trophy_mint_deposit,
+        );
+        let economics = (
+            self.min_deposit,
+            self.max_total_deposit,
+            self.win_fee_bps,
+            self.immunity_price_per_block,
+            self.max_immunity_blocks,
+            self.win_reward_base,
+            self.win_reward_per_block,
+            self.keeper_reward,
+        );
+        let config = near_sdk::borsh::to_vec(&(agents, limits, rules, integrations, economics))
+            .expect("Failed to serialize config");
+        near_sdk::bs58::encode(env::sha256_array(&config)).into_string()
+    }
+
+    /// Every `UpdateConfig` field as `(name, JSON value)`, sorted by name, so
+    /// a desired config can be diffed against it and the differences sent to
+    /// `update_config` as is. Keep in sync with `UpdateConfig`.
+    pub fn get_config_kv(&self) -> Vec<(String, String)> {
+        let mut entries = vec![
+            config_entry("system_prompt", &self.agent_system_prompt),
+            config_entry(
+                "min_blocks_between_prompt_changes",
+                &self.min_blocks_between_prompt_changes,
+            ),
+            config_entry("maintenance", &self.maintenance),
+            config_entry("feature_flags", &self.feature_flags),
+            config_entry("response_deadline_blocks", &self.response_deadline_blocks),
+            config_entry("timeout_response", &self.timeout_response),
+            config_entry("min_champion_len", &self.min_champion_len),
+            config_entry("min_champions_to_start", &self.min_champions_to_start),
+            config_entry("crowned_cooldown_blocks", &self.crowned_cooldown_blocks),
+            config_entry("repeat_guess_window", &self.repeat_guess_window),
+            config_entry("claim_lease_blocks", &self.claim_lease_blocks),
+            config_entry("max_bonus_points", &self.max_bonus_points),
+            config_entry("min_prepaid_gas", &self.min_prepaid_gas),
+            config_entry("yield_callback_gas", &self.yield_callback_gas),
+            config_entry("max_open_requests", &self.max_open_requests),
+            config_entry("max_queue_length", &self.max_queue_length),
+            config_entry("decay_blocks", &self.decay_blocks),
+            config_entry(
+                "max_consecutive_agent_errors",
+                &self.max_consecutive_agent_errors,
+            ),
+            config_entry("target_champion", &self.target_champion),
+            config_entry("question_template", &self.question_template),
+            config_entry("event_forwarder", &self.event_forwarder),
+            config_entry("trophy_nft_contract", &self.trophy_nft_contract),
+            config_entry("trophy_mint_deposit", &self.trophy_mint_deposit),
+            config_entry("min_deposit", &self.min_deposit),
+            config_entry("max_total_deposit", &self.max_total_deposit),
+            config_entry("win_fee_bps", &self.win_fee_bps),
+            config_entry("immunity_price_per_block", &self.immunity_price_per_block),
+            config_entry("max_immunity_blocks", &self.max_immunity_blocks),
+            config_entry("win_reward_base", &self.win_reward_base),
+            config_entry("win_reward_per_block", &self.win_reward_per_block),
+            config_entry("keeper_reward", &self.keeper_reward),
+            config_entry("dominance_weights", &self.dominance_weights),
+            config_entry("tie_break", &self.tie_break),
+        ];
+        entries.sort();
+        entries
+    }
+
+    /// Applies several settings in one transaction so no intermediate mix of
+    /// old and new values is ever observable.
+    pub fn update_config(&mut self, config: UpdateConfig) {
+        self.assert_owner();
+
+        if let Some(system_prompt) = config.system_prompt {
+            self.agent_system_prompt = system_prompt;
+            self.last_prompt_change_block = env::block_height();
+        }
+        if let Some(blocks) = config.min_blocks_between_prompt_changes {
+            self.min_blocks_between_prompt_changes = blocks;
+        }
+        if let Some(maintenance) = config.maintenance {
+            self.maintenance = maintenance;
+        }
+        if let Some(feature_flags) = config.feature_flags {
+            self.feature_flags = feature_flags;
+        }
+        if let Some(blocks) = config.response_deadline_blocks {
+            self.set_response_deadline_blocks_checked(blocks);
+        }
+        if let Some(timeout_response) = config.timeout_response {
+            self.store_timeout_response(timeout_response);
+        }
+        if let Some(window) = config.repeat_guess_window {
+            self.set_repeat_guess_window_checked(window);
+        }
+        if let Some(blocks) = config.claim_lease_blocks {
+            self.claim_lease_blocks = blocks;
+        }
+        if let Some(tie_break) = config.tie_break {
+            self.tie_break = tie_break;
+        }
+        if let Some(min_champion_len) = config.min_champion_len {
+            self.min_champion_len = min_champion_len;
+        }
+        if let Some(min_champions_to_start) = config.min_champions_to_start {
+            self.min_champions_to_start = min_champions_to_start;
+        }
+        if let Some(blocks) = config.crowned_cooldown_blocks {
+            self.crowned_cooldown_blocks = blocks;
+        }
+        if let Some(yield_callback_gas) = config.yield_callback_gas {
+            self.set_yield_callback_gas_checked(yield_callback_gas);
+        }
+        if let Some(min_prepaid_gas) = config.min_prepaid_gas {
+            self.set_min_prepaid_gas_checked(min_prepaid_gas);
+        }
+        if let Some(max_open_requests) = config.max_open_requests {
+            self.max_open_requests = max_open_requests;
+        }
+        if let Some(max_queue_length) = config.max_queue_length {
+            self.max_queue_length = max_queue_length;
+        }
+        if let Some(decay_blocks) = config.decay_blocks {
+            self.decay_blocks = decay_blocks;
+        }
+        if let Some(max) = config.max_consecutive_agent_errors {
+            self.max_consecutive_agent_errors = max;
+        }
+        if let Some(target_champion) = config.target_champion {
+            self.target_champion = target_champion.map(|word| word.to_lowercase());
+        }
+        if let Some(question_template) = config.question_template {
+            self.set_question_template_checked(question_template);
+        }
+        if let Some(event_forwarder) = config.event_forwarder {
+            self.event_forwarder = event_forwarder;
+        }
+        if let Some(trophy_nft_contract) = config.trophy_nft_contract {
+            self.set_trophy_nft_contract_checked(trophy_nft_contract);
+        }
+        if let Some(trophy_mint_deposit) = config.trophy_mint_deposit {
+            self.trophy_mint_deposit = trophy_mint_deposit;
+        }
+        if let Some(min_deposit) = config.min_deposit {
+            self.min_deposit = min_deposit;
+        }
+        if let Some(max_total_deposit) = config.max_total_deposit {
+            self.max_total_deposit = max_total_deposit;
+        }
+        if let Some(win_fee_bps) = config.win_fee_bps {
+            self.set_win_fee_bps_checked(win_fee_bps);
+        }
+        if let Some(price_per_block) = config.immunity_price_per_block {
+            self.immunity_price_per_block = price_per_block;
+        }
+        if let Some(max_blocks) = config.max_immunity_blocks {
+            self.max_immunity_blocks = max_blocks;
+        }
+        if let Some(base) = config.win_reward_base {
+            self.win_reward_base = base;
+        }
+        if let Some(per_block) = config.win_reward_per_block {
+            self.win_reward_per_block = per_block;
+        }
+        if let Some(keeper_reward) = config.keeper_reward {
+            self.keeper_reward = keeper_reward;
+        }
+        if let Some(max_bonus_points) = config.max_bonus_points {
+            self.max_bonus_points = max_bonus_points;
+        }
+        if let Some(weights) = config.dominance_weights {
+            self.dominance_weights = weights;
+        }
+    }
+}
+
+fn config_entry<T: Serialize>(key: &str, value: &T) -> (String, String) {
+    (key.to_string(), serde_json::to_string(value).unwrap())
+}
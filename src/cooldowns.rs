@@ -0,0 +1,75 @@
+use crate::*;
+
+/// Remaining blocks of every cooldown that applies to an account, `0` when
+/// the action is currently allowed.
+#[derive(Serialize, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Cooldowns {
+    pub prompt_change: u64,
+    /// Words that cannot be guessed yet since they were just crowned, see
+    /// `set_crowned_cooldown_blocks`, with the blocks left for each.
+    pub recently_crowned_remaining: Vec<(String, u64)>,
+    /// Blocks until the current champion can be challenged again.
+    pub champion_immunity_remaining: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_cooldowns(&self, account_id: AccountId) -> Cooldowns {
+        Cooldowns {
+            prompt_change: if account_id == self.owner_id {
+                0
+            } else {
+                self.prompt_change_cooldown()
+            },
+            recently_crowned_remaining: self
+                .recently_crowned
+                .iter()
+                .filter(|(_, until)| *until > env::block_height())
+                .map(|(word, until)| (word.clone(), until - env::block_height()))
+                .collect(),
+            champion_immunity_remaining: (self.immune_until_block + 1)
+                .saturating_sub(env::block_height()),
+        }
+    }
+
+    pub fn get_crowned_cooldown_blocks(&self) -> u64 {
+        self.crowned_cooldown_blocks
+    }
+
+    /// Rejects guessing a freshly crowned word for `blocks` blocks after it
+    /// takes the title, on top of any permanent past-champion rules. Zero
+    /// disables the cooldown; words crowned before a change keep their expiry.
+    pub fn set_crowned_cooldown_blocks(&mut self, blocks: u64) {
+        self.assert_owner();
+        self.crowned_cooldown_blocks = blocks;
+    }
+}
+
+impl Contract {
+    pub(crate) fn is_recently_crowned(&self, word: &str) -> bool {
+        self.recently_crowned
+            .iter()
+            .any(|(crowned, until)| crowned == word && *until > env::block_height())
+    }
+
+    /// Starts the crowning cooldown of the current champion, dropping expired entries so
+    /// the list only holds words crowned within the cooldown.
+    pub(crate) fn start_crowned_cooldown(&mut self) {
+        let block_height = env::block_height();
+        self.recently_crowned
+            .retain(|(_, until)| *until > block_height);
+        if self.crowned_cooldown_blocks > 0 {
+            self.recently_crowned.push_back((
+                self.current_champion.clone(),
+                block_height + self.crowned_cooldown_blocks,
+            ));
+        }
+    }
+
+    /// Blocks until the operator may change the prompt again.
+    pub(crate) fn prompt_change_cooldown(&self) -> u64 {
+        (self.last_prompt_change_block + self.min_blocks_between_prompt_changes)
+            .saturating_sub(env::block_height())
+    }
+}
@@ -0,0 +1,193 @@
+use crate::*;
+
+/// Roughly the protocol timeout of a yielded promise, after which it
+/// resumes on its own and can no longer take a response.
+pub(crate) const YIELD_TIMEOUT_BLOCKS: u64 = 200;
+pub(crate) const DEFAULT_RESPONSE_DEADLINE_BLOCKS: u64 = YIELD_TIMEOUT_BLOCKS;
+/// Expired requests resolved opportunistically by each new `request`.
+pub(crate) const MAX_TIMEOUTS_PER_REQUEST: u64 = 2;
+/// Bounds how many already resolved ids a single cleanup pass may skip.
+const MAX_TIMEOUT_SCAN: u64 = 10;
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_response_deadline_blocks(&self) -> u64 {
+        self.response_deadline_blocks
+    }
+
+    /// At most `YIELD_TIMEOUT_BLOCKS`, a response after that cannot resume
+    /// the yield anymore.
+    pub fn set_response_deadline_blocks(&mut self, blocks: u64) {
+        self.assert_owner();
+        self.set_response_deadline_blocks_checked(blocks);
+    }
+
+    /// Response an expired request resolves with, a loss with no data by default.
+    pub fn get_timeout_response(&self) -> Response {
+        self.timeout_response.clone()
+    }
+
+    pub fn set_timeout_response(&mut self, response: Response) {
+        self.assert_owner();
+        self.store_timeout_response(response);
+    }
+
+    /// Permissionless keeper entry point: times out up to `max` expired open
+    /// requests, oldest first, with the configured timeout response. Each
+    /// call looks at no more than `MAX_TIMEOUT_SCAN` request ids, so a large
+    /// backlog takes several calls. Returns the number of requests timed out.
+    pub fn sweep_expired(&mut self, max: u64) -> u64 {
+        self.timeout_expired_requests(max)
+    }
+
+    /// Permissionless: resolves `request_id` with the configured timeout
+    /// response once it is past its deadline, without waiting for the
+    /// in-order sweep to reach it. Pays the caller `keeper_reward` from the
+    /// prize pool, at most what the pool holds, unless the caller made the
+    /// request.
+    pub fn force_timeout(&mut self, request_id: RequestId) {
+        self.assert_maintenance();
+        require!(request_id < self.num_requests, "ERR_BAD_REQUEST_ID");
+        let request = self
+            .requests
+            .get(&request_id)
+            .expect("Request ID not found");
+        require!(
+            !self.responses.contains_key(&request_id),
+            "ERR_ALREADY_RESPONDED"
+        );
+        require!(self.is_expired(&request), "ERR_NOT_EXPIRED");
+
+        self.close_request(request_id, &request, self.timeout_response.clone());
+        // Otherwise players could farm the reward off their own requests.
+        let keeper = env::predecessor_account_id();
+        if keeper != request.originator_id {
+            let reward = self.keeper_reward.min(self.prize_pool);
+            self.pay_reward(&keeper, reward);
+        }
+    }
+
+    pub fn get_keeper_reward(&self) -> NearToken {
+        self.keeper_reward
+    }
+
+    pub fn set_keeper_reward(&mut self, keeper_reward: NearToken) {
+        self.assert_owner();
+        self.keeper_reward = keeper_reward;
+    }
+
+    /// Gives the agent `extra_blocks` more for a hard guess. The whole window
+    /// stays within `YIELD_TIMEOUT_BLOCKS`, as the protocol resumes the yield
+    /// on its own after that, so this only helps when
+    /// `response_deadline_blocks` is set below it. Returns the new deadline
+    /// block.
+    pub fn extend_deadline(&mut self, request_id: RequestId, extra_blocks: u64) -> BlockHeight {
+        require!(request_id < self.num_requests, "ERR_BAD_REQUEST_ID");
+        self.assert_operator();
+
+        let mut request = self
+            .requests
+            .get(&request_id)
+            .expect("Request ID not found");
+        request.deadline_extension = request.deadline_extension.saturating_add(extra_blocks);
+        require!(
+            self.response_deadline_blocks
+                .saturating_add(request.deadline_extension)
+                <= YIELD_TIMEOUT_BLOCKS,
+            "ERR_DEADLINE_PAST_YIELD_TIMEOUT"
+        );
+        self.requests.insert(&request_id, &request);
+
+        let deadline_block = self.deadline_block(&request);
+        events::emit::deadline_extended(self.event_forwarder.as_ref(), request_id, deadline_block);
+        deadline_block
+    }
+
+    /// Whether `request_id` is still open and past its response deadline.
+    /// Such a request is timed out by one of the next `request` calls, or
+    /// right away with `force_timeout`.
+    pub fn is_request_expired(&self, request_id: RequestId) -> bool {
+        self.requests
+            .get(&request_id)
+            .is_some_and(|request| self.is_expired(&request))
+    }
+}
+
+impl Contract {
+    /// Stored unsigned: `await_response` tells contract-closed requests from
+    /// agent rulings by the missing signature.
+    pub(crate) fn store_timeout_response(&mut self, response: Response) {
+        self.timeout_response = Response {
+            signature: None,
+            challenge: None,
+            ..response
+        };
+    }
+
+    pub(crate) fn set_response_deadline_blocks_checked(&mut self, blocks: u64) {
+        require!(blocks > 0, "ERR_ZERO_DEADLINE");
+        require!(
+            blocks <= YIELD_TIMEOUT_BLOCKS,
+            "ERR_DEADLINE_PAST_YIELD_TIMEOUT"
+        );
+        self.response_deadline_blocks = blocks;
+    }
+
+    pub(crate) fn deadline_block(&self, request: &Request) -> BlockHeight {
+        request.created_block + self.response_deadline_blocks + request.deadline_extension
+    }
+
+    pub(crate) fn is_expired(&self, request: &Request) -> bool {
+        env::block_height() > self.deadline_block(request)
+    }
+
+    /// Walks open requests from the oldest one and times out up to `max` of
+    /// those past their deadline. Stops at the first open request that has not
+    /// expired, so an extended deadline also holds back later requests. Returns the number of requests timed out.
+    pub(crate) fn timeout_expired_requests(&mut self, max: u64) -> u64 {
+        let mut timed_out = 0;
+        let mut scanned = 0;
+
+        while timed_out < max
+            && scanned < MAX_TIMEOUT_SCAN
+            && self.oldest_open_request < self.num_requests
+        {
+            scanned += 1;
+            let request_id = self.oldest_open_request;
+
+            if let Some(request) = self.requests.get(&request_id) {
+                if !self.is_expired(&request) {
+                    break;
+                }
+                if !self.responses.contains_key(&request_id) {
+                    self.close_request(request_id, &request, self.timeout_response.clone());
+                    timed_out += 1;
+                }
+            }
+
+            self.oldest_open_request += 1;
+        }
+
+        timed_out
+    }
+}
+
+pub(crate) fn cancel_response() -> Response {
+    Response {
+        ok: false,
+        data: Some("cancelled".to_string()),
+        signature: None,
+        challenge: None,
+        originator: None,
+    }
+}
+
+pub(crate) fn default_timeout_response() -> Response {
+    Response {
+        ok: false,
+        data: None,
+        signature: None,
+        challenge: None,
+        originator: None,
+    }
+}
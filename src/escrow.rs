@@ -0,0 +1,200 @@
+use crate::*;
+use near_sdk::{Promise, StorageUsage};
+
+/// Ceiling of `win_fee_bps`, the whole escrow.
+const MAX_FEE_BPS: u32 = 10_000;
+
+#[near_bindgen]
+impl Contract {
+    /// Deposit held for an open request, zero once it has been settled.
+    pub fn get_escrow(&self, request_id: RequestId) -> NearToken {
+        self.escrows
+            .get(&request_id)
+            .unwrap_or(NearToken::from_yoctonear(0))
+    }
+
+    /// Storage stake held for an open request, see the `storage_staking` flag.
+    pub fn get_storage_stake(&self, request_id: RequestId) -> NearToken {
+        self.storage_stakes
+            .get(&request_id)
+            .unwrap_or(NearToken::from_yoctonear(0))
+    }
+
+    /// Lifetime volume of deposits attached to `request`, refunds included.
+    pub fn get_total_collected(&self) -> NearToken {
+        self.total_collected
+    }
+
+    /// Lifetime volume of deposits `account_id` attached to `request`.
+    pub fn get_account_deposits(&self, account_id: AccountId) -> NearToken {
+        self.account_deposits
+            .get(&account_id)
+            .unwrap_or(NearToken::from_yoctonear(0))
+    }
+
+    pub fn get_max_total_deposit(&self) -> Option<NearToken> {
+        self.max_total_deposit
+    }
+
+    /// Caps the lifetime deposits of every account, refunds included.
+    /// Requests past the cap are rejected. `None` lifts the cap.
+    pub fn set_max_total_deposit(&mut self, max_total_deposit: Option<NearToken>) {
+        self.assert_owner();
+        self.max_total_deposit = max_total_deposit;
+    }
+
+    pub fn get_prize_pool(&self) -> NearToken {
+        self.prize_pool
+    }
+
+    /// Paid from the prize pool for dethroning the current champion right
+    /// now: `win_reward_base` plus `win_reward_per_block` for every block of
+    /// its reign, at most the whole pool.
+    pub fn get_potential_reward(&self) -> NearToken {
+        let reign_blocks = env::block_height() - self.champion_since_block;
+        self.win_reward_per_block
+            .saturating_mul(reign_blocks as u128)
+            .saturating_add(self.win_reward_base)
+            .min(self.prize_pool)
+    }
+
+    pub fn get_win_reward(&self) -> (NearToken, NearToken) {
+        (self.win_reward_base, self.win_reward_per_block)
+    }
+
+    pub fn set_win_reward(&mut self, base: NearToken, per_block: NearToken) {
+        self.assert_owner();
+        self.win_reward_base = base;
+        self.win_reward_per_block = per_block;
+    }
+
+    pub fn get_min_deposit(&self) -> NearToken {
+        self.min_deposit
+    }
+
+    pub fn set_min_deposit(&mut self, min_deposit: NearToken) {
+        self.assert_owner();
+        self.min_deposit = min_deposit;
+    }
+
+    pub fn get_win_fee_bps(&self) -> u32 {
+        self.win_fee_bps
+    }
+
+    /// Share of a winning escrow kept for the prize pool, in basis points.
+    pub fn set_win_fee_bps(&mut self, win_fee_bps: u32) {
+        self.assert_owner();
+        self.set_win_fee_bps_checked(win_fee_bps);
+    }
+}
+
+impl Contract {
+    pub(crate) fn set_win_fee_bps_checked(&mut self, win_fee_bps: u32) {
+        require!(win_fee_bps <= MAX_FEE_BPS, "ERR_FEE_TOO_HIGH");
+        self.win_fee_bps = win_fee_bps;
+    }
+
+    /// Adds `deposit` to the lifetime total of `account_id`, rejecting it if
+    /// that would exceed `max_total_deposit`.
+    pub(crate) fn record_account_deposit(&mut self, account_id: &AccountId, deposit: NearToken) {
+        let total = self
+            .get_account_deposits(account_id.clone())
+            .saturating_add(deposit);
+        require!(
+            self.max_total_deposit.is_none_or(|max| total <= max),
+            "ERR_DEPOSIT_CAP_REACHED"
+        );
+        if !deposit.is_zero() {
+            self.account_deposits.insert(account_id, &total);
+        }
+    }
+
+    /// Moves the cost of the storage added since `storage_before` from the
+    /// escrow of a new request into its storage stake, at most the whole
+    /// deposit. Set `min_deposit` to cover a request's storage.
+    pub(crate) fn stake_storage(
+        &mut self,
+        request_id: RequestId,
+        deposit: NearToken,
+        storage_before: StorageUsage,
+    ) {
+        if !self.feature_flags.storage_staking {
+            return;
+        }
+        let stake = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage() - storage_before) as u128)
+            .min(deposit);
+        if stake.is_zero() {
+            return;
+        }
+
+        self.escrows
+            .insert(&request_id, &deposit.saturating_sub(stake));
+        self.storage_stakes.insert(&request_id, &stake);
+    }
+
+    /// Refunds the cost of the storage freed since `storage_before` to
+    /// `account_id`, at most the stake. Called from `remove_open_request`, so
+    /// every cleanup path goes through it. The rest of the stake pays for the
+    /// timeline and audit log entries that stay behind.
+    pub(crate) fn refund_storage(
+        &mut self,
+        request_id: RequestId,
+        account_id: &AccountId,
+        storage_before: StorageUsage,
+    ) {
+        let Some(stake) = self.storage_stakes.remove(&request_id) else {
+            return;
+        };
+        let refund = env::storage_byte_cost()
+            .saturating_mul(storage_before.saturating_sub(env::storage_usage()) as u128)
+            .min(stake);
+        if !refund.is_zero() {
+            Promise::new(account_id.clone()).transfer(refund).detach();
+        }
+    }
+
+    pub(crate) fn pay_reward(&mut self, account_id: &AccountId, reward: NearToken) {
+        if reward.is_zero() {
+            return;
+        }
+        self.prize_pool = self.prize_pool.saturating_sub(reward);
+        Promise::new(account_id.clone()).transfer(reward).detach();
+    }
+
+    /// Releases the escrow of a resolved request, see `distribute_deposit`.
+    pub(crate) fn settle_escrow(
+        &mut self,
+        request_id: RequestId,
+        request: &Request,
+        outcome: &Outcome,
+    ) {
+        if let Some(deposit) = self.escrows.remove(&request_id) {
+            self.distribute_deposit(&request.originator_id, deposit, outcome);
+        }
+    }
+
+    /// A loss moves the deposit to the prize pool, a win refunds it minus
+    /// `win_fee_bps` for the pool, and anything unresolved is refunded whole.
+    /// A superseded win is refunded like a win, without the reward.
+    pub(crate) fn distribute_deposit(
+        &mut self,
+        account_id: &AccountId,
+        deposit: NearToken,
+        outcome: &Outcome,
+    ) {
+        let to_pool = match outcome {
+            Outcome::Won | Outcome::Superseded => deposit
+                .saturating_mul(self.win_fee_bps as u128)
+                .saturating_div(MAX_FEE_BPS as u128),
+            Outcome::Lost => deposit,
+            Outcome::Unresolved => NearToken::from_yoctonear(0),
+        };
+        self.prize_pool = self.prize_pool.saturating_add(to_pool);
+
+        let refund = deposit.saturating_sub(to_pool);
+        if !refund.is_zero() {
+            Promise::new(account_id.clone()).transfer(refund).detach();
+        }
+    }
+}
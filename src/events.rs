@@ -3,7 +3,10 @@ use crate::*;
 pub mod emit {
     use super::*;
     use near_sdk::serde_json::json;
-    use near_sdk::{env, log, AccountId};
+    use near_sdk::{log, AccountId, Promise};
+
+    /// Gas budget of the mirrored `on_event` call, none of the leftover gas is shared with it.
+    const EVENT_FORWARD_GAS: Gas = Gas::from_tgas(5);
 
     #[derive(Serialize)]
     #[serde(crate = "near_sdk::serde")]
@@ -14,6 +17,7 @@ pub mod emit {
         pub max_iterations: &'a Option<u8>,
         pub thread_id: &'a Option<String>,
         pub env_vars: &'a Option<String>,
+        pub response_deadline_block: BlockHeight,
 
         signer_id: &'a AccountId,
         referral_id: &'a Option<AccountId>,
@@ -21,7 +25,56 @@ pub mod emit {
         pub amount: Option<u128>,
     }
 
-    fn log_event<T: Serialize>(event: &str, data: T) {
+    #[derive(Serialize)]
+    #[serde(crate = "near_sdk::serde")]
+    struct GameWonData<'a> {
+        pub champion: &'a String,
+        pub owner_id: &'a AccountId,
+    }
+
+    /// Full creation record, so indexers need no follow-up `get_request`.
+    /// `data_id` is encoded the same way `respond` takes it.
+    #[derive(Serialize)]
+    #[serde(crate = "near_sdk::serde")]
+    struct RequestCreatedData<'a> {
+        pub request_id: RequestId,
+        pub originator_id: &'a AccountId,
+        pub message: &'a String,
+        pub champion_at_request: &'a String,
+        pub data_id: &'a CryptoHash,
+        pub tags: &'a Vec<String>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(crate = "near_sdk::serde")]
+    struct DeadlineExtendedData {
+        pub request_id: RequestId,
+        pub response_deadline_block: BlockHeight,
+    }
+
+    #[derive(Serialize)]
+    #[serde(crate = "near_sdk::serde")]
+    struct ChampionDecayedData<'a> {
+        pub decayed: &'a String,
+        pub champion: &'a String,
+    }
+
+    #[derive(Serialize)]
+    #[serde(crate = "near_sdk::serde")]
+    struct SeasonStartedData<'a> {
+        pub season: u64,
+        pub champion: &'a String,
+        pub agent: &'a String,
+        pub cancelled_requests: u64,
+    }
+
+    #[derive(Serialize)]
+    #[serde(crate = "near_sdk::serde")]
+    struct AgentDownData {
+        pub consecutive_errors: u64,
+    }
+
+    fn log_event<T: Serialize>(forwarder: Option<&AccountId>, event: &str, data: T) {
         let event = json!({
             "standard": "nearai",
             "version": "0.1.0",
@@ -30,10 +83,33 @@ pub mod emit {
         });
 
         log!("EVENT_JSON:{}", event.to_string());
+
+        // Detached: a failing forwarder never affects the emitting call.
+        if let Some(forwarder) = forwarder {
+            Promise::new(forwarder.clone())
+                .function_call_weight(
+                    "on_event",
+                    json!({ "event_json": event.to_string() })
+                        .to_string()
+                        .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    EVENT_FORWARD_GAS,
+                    GasWeight(0),
+                )
+                .detach();
+        }
     }
 
-    pub fn run_agent(agent: &String, message: &String, request_id: Option<RequestId>) {
+    pub fn run_agent(
+        forwarder: Option<&AccountId>,
+        agent: &String,
+        message: &String,
+        request_id: Option<RequestId>,
+        signer_id: &AccountId,
+        response_deadline_block: BlockHeight,
+    ) {
         log_event(
+            forwarder,
             "run_agent",
             AgentData {
                 message,
@@ -42,12 +118,86 @@ pub mod emit {
                 max_iterations: &None,
                 thread_id: &None,
                 env_vars: &None,
-                signer_id: &env::predecessor_account_id(),
+                response_deadline_block,
+                signer_id,
                 referral_id: &None,
                 amount: None,
             },
         );
     }
+
+    pub fn request_created(
+        forwarder: Option<&AccountId>,
+        request_id: RequestId,
+        request: &Request,
+    ) {
+        log_event(
+            forwarder,
+            "request_created",
+            RequestCreatedData {
+                request_id,
+                originator_id: &request.originator_id,
+                message: &request.message,
+                champion_at_request: &request.champion_at_request,
+                data_id: &request.data_id,
+                tags: &request.tags,
+            },
+        );
+    }
+
+    pub fn deadline_extended(
+        forwarder: Option<&AccountId>,
+        request_id: RequestId,
+        response_deadline_block: BlockHeight,
+    ) {
+        log_event(
+            forwarder,
+            "deadline_extended",
+            DeadlineExtendedData {
+                request_id,
+                response_deadline_block,
+            },
+        );
+    }
+
+    pub fn champion_decayed(forwarder: Option<&AccountId>, decayed: &String, champion: &String) {
+        log_event(
+            forwarder,
+            "champion_decayed",
+            ChampionDecayedData { decayed, champion },
+        );
+    }
+
+    pub fn agent_down(forwarder: Option<&AccountId>, consecutive_errors: u64) {
+        log_event(
+            forwarder,
+            "agent_down",
+            AgentDownData { consecutive_errors },
+        );
+    }
+
+    pub fn season_started(
+        forwarder: Option<&AccountId>,
+        season: u64,
+        champion: &String,
+        agent: &String,
+        cancelled_requests: u64,
+    ) {
+        log_event(
+            forwarder,
+            "season_started",
+            SeasonStartedData {
+                season,
+                champion,
+                agent,
+                cancelled_requests,
+            },
+        );
+    }
+
+    pub fn game_won(forwarder: Option<&AccountId>, champion: &String, owner_id: &AccountId) {
+        log_event(forwarder, "game_won", GameWonData { champion, owner_id });
+    }
 }
 
 pub mod option_u128_dec_format {
@@ -0,0 +1,66 @@
+use crate::*;
+
+/// Owner-controlled toggles for optional game rules. Fields missing from the
+/// JSON passed to `set_feature_flags` take their default value.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde", default)]
+pub struct FeatureFlags {
+    /// Lets the operator submit guesses. The operator relays every agent
+    /// response, so once guesses carry value this is a self-dealing risk.
+    pub operator_can_play: bool,
+    /// Rejects requests relayed through another contract.
+    pub require_direct_call: bool,
+    /// Queues requests beyond `max_open_requests` instead of rejecting them.
+    pub queue_when_overloaded: bool,
+    /// Rejects guesses that contain or are contained in the current champion.
+    pub reject_substring_guesses: bool,
+    /// Rejects responses to requests the agent never acknowledged through
+    /// `ack_request`, so the operator cannot fabricate rulings on its own.
+    pub require_ack: bool,
+    /// Rejects responses whose `originator` is missing or differs from the
+    /// originator of the request they answer.
+    pub require_matching_originator: bool,
+    /// Rejects responses that do not echo the challenge of the request they
+    /// answer, so the operator cannot pre-sign or replay rulings. Off by
+    /// default: agents that predate challenges sign no challenge at all.
+    pub require_challenge: bool,
+    /// Takes the storage cost of each new request out of its deposit and
+    /// refunds the freed part when the request is cleaned up.
+    pub storage_staking: bool,
+    /// Rejects a guess while the same guess is already an open request.
+    /// Queued requests are not open yet and do not count.
+    pub reject_duplicate_in_flight: bool,
+    /// Rejects a guess the account made within its last
+    /// `repeat_guess_window` guesses.
+    pub reject_repeat_guesses: bool,
+}
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        Self {
+            operator_can_play: true,
+            require_direct_call: false,
+            queue_when_overloaded: false,
+            reject_substring_guesses: false,
+            require_ack: false,
+            require_matching_originator: false,
+            require_challenge: false,
+            storage_staking: false,
+            reject_duplicate_in_flight: false,
+            reject_repeat_guesses: false,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_feature_flags(&self) -> FeatureFlags {
+        self.feature_flags.clone()
+    }
+
+    pub fn set_feature_flags(&mut self, feature_flags: FeatureFlags) {
+        self.assert_owner();
+        self.feature_flags = feature_flags;
+    }
+}
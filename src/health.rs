@@ -0,0 +1,89 @@
+use crate::*;
+
+/// Every contract-wide condition currently holding back or reshaping
+/// requests, for status pages. `is_ready` condenses the blocking ones.
+#[derive(Serialize, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GatingStatus {
+    pub paused: bool,
+    pub maintenance: bool,
+    /// The pause was triggered by too many failed agent rulings in a row.
+    pub agent_down: bool,
+    /// The pause was triggered by crowning `target_champion`.
+    pub target_reached: bool,
+    /// The primary agent lacks a name, public key or system prompt.
+    pub agent_unconfigured: bool,
+    /// `max_open_requests` is reached: new requests are queued or rejected.
+    pub overloaded: bool,
+    /// The champion bought immunity and cannot be challenged.
+    pub champion_immune: bool,
+    /// Anyone may revert the current champion with `decay_champion`.
+    pub decay_pending: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_gating_status(&self) -> GatingStatus {
+        let champion_immune = self.is_champion_immune();
+        GatingStatus {
+            paused: self.paused,
+            maintenance: self.maintenance,
+            agent_down: self.paused
+                && self
+                    .max_consecutive_agent_errors
+                    .is_some_and(|max| self.consecutive_agent_errors > max),
+            target_reached: self.paused
+                && self.target_champion.as_ref() == Some(&self.current_champion),
+            agent_unconfigured: self.agent_name.is_empty()
+                || self.agent_public_key.is_empty()
+                || self.agent_system_prompt.is_empty(),
+            overloaded: self.is_overloaded(),
+            champion_immune,
+            decay_pending: !self.paused
+                && !self.maintenance
+                && !champion_immune
+                && self.decay_blocks.is_some_and(|decay_blocks| {
+                    env::block_height() >= self.champion_since_block.saturating_add(decay_blocks)
+                })
+                && self.champion_history.len() >= 2,
+        }
+    }
+
+    /// Failed agent rulings in a row since the last successful one.
+    pub fn get_consecutive_agent_errors(&self) -> u64 {
+        self.consecutive_agent_errors
+    }
+
+    pub fn get_max_consecutive_agent_errors(&self) -> Option<u64> {
+        self.max_consecutive_agent_errors
+    }
+
+    /// Pauses the contract once more than `max` agent rulings in a row fail,
+    /// `None` disables the check.
+    pub fn set_max_consecutive_agent_errors(&mut self, max: Option<u64>) {
+        self.assert_owner();
+        self.max_consecutive_agent_errors = max;
+    }
+}
+
+impl Contract {
+    /// Counts an agent ruling that was `ok: false` or could not be applied as
+    /// an error and pauses new requests when the agent looks down. Timeouts
+    /// and cancellations are not agent rulings and leave the counter alone.
+    pub(crate) fn record_agent_health(&mut self, ok: bool) {
+        if ok {
+            self.consecutive_agent_errors = 0;
+            return;
+        }
+
+        self.consecutive_agent_errors += 1;
+        if !self.paused
+            && self
+                .max_consecutive_agent_errors
+                .is_some_and(|max| self.consecutive_agent_errors > max)
+        {
+            self.paused = true;
+            events::emit::agent_down(self.event_forwarder.as_ref(), self.consecutive_agent_errors);
+        }
+    }
+}
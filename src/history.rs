@@ -0,0 +1,348 @@
+use crate::*;
+
+/// Most records accepted by a single `import_champions` call.
+pub(crate) const MAX_IMPORT_BATCH: u64 = 100;
+
+/// `champion_reason` of a champion restored by `decay_champion`.
+pub(crate) const DECAY_REASON: &str = "decayed";
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct ChampionRecord {
+    pub champion: String,
+    #[schemars(with = "String")]
+    pub owner_id: AccountId,
+    pub block_height: BlockHeight,
+    /// History index of the reign this one replaced, which `decay_champion`
+    /// restores. Set by the contract, ignored on import.
+    #[serde(default)]
+    pub predecessor: Option<u64>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct Reign {
+    pub champion: String,
+    #[schemars(with = "String")]
+    pub owner_id: AccountId,
+    pub blocks: u64,
+}
+
+/// Everything a champion banner shows, in one call.
+#[derive(Serialize, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ChampionProfile {
+    pub word: String,
+    #[schemars(with = "String")]
+    pub owner: AccountId,
+    pub since_block: BlockHeight,
+    pub reign_blocks: u64,
+    pub reason: Option<String>,
+    /// Times this word has been crowned, the current reign included.
+    pub reign_count: u64,
+    /// See `get_champion_win_rate`.
+    pub win_rate: Option<u32>,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_current_champion_profile(&self) -> ChampionProfile {
+        ChampionProfile {
+            word: self.current_champion.clone(),
+            owner: self.champion_owner.clone(),
+            since_block: self.champion_since_block,
+            reign_blocks: env::block_height() - self.champion_since_block,
+            reason: self.champion_reason.clone(),
+            reign_count: self.reign_counts.get(&self.current_champion).unwrap_or(0),
+            win_rate: self.get_champion_win_rate(self.current_champion.clone()),
+        }
+    }
+
+    /// Words `word` dethroned over the game's history, oldest first. A word
+    /// beaten on several reigns appears once per reign.
+    pub fn get_conquests(
+        &self,
+        word: String,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<String> {
+        let Some(conquests) = self.conquests.get(&word.to_lowercase()) else {
+            return vec![];
+        };
+        let from_index = from_index.unwrap_or(0);
+
+        (from_index
+            ..std::cmp::min(
+                from_index.saturating_add(page_limit(limit)),
+                conquests.len(),
+            ))
+            .filter_map(|index| conquests.get(index))
+            .collect()
+    }
+
+    pub fn get_min_champions_to_start(&self) -> u64 {
+        self.min_champions_to_start
+    }
+
+    /// Warm-up: until the champion set holds `min_champions_to_start` words,
+    /// winning guesses join the set and are counted as wins, but the current
+    /// champion keeps the title. Zero disables the warm-up.
+    pub fn set_min_champions_to_start(&mut self, min_champions_to_start: u64) {
+        self.assert_owner();
+        self.min_champions_to_start = min_champions_to_start;
+    }
+
+    /// Longest completed reign; the current champion counts once dethroned.
+    pub fn get_longest_reign(&self) -> Option<Reign> {
+        self.longest_reign.clone()
+    }
+
+    /// Average length of completed reigns in blocks, `0` before the first change.
+    pub fn get_avg_reign_blocks(&self) -> u64 {
+        self.total_reign_blocks
+            .checked_div(self.completed_reigns)
+            .unwrap_or(0)
+    }
+
+    /// Champion changes crowned within `from_block..=to_block`, oldest first.
+    pub fn get_champion_changes(
+        &self,
+        from_block: BlockHeight,
+        to_block: BlockHeight,
+        limit: Option<u64>,
+    ) -> Vec<ChampionRecord> {
+        let limit = page_limit(limit);
+
+        (self.first_record_at_or_after(from_block)..self.champion_history.len())
+            .filter_map(|index| self.champion_history.get(index))
+            .take_while(|record| record.block_height <= to_block)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn get_decay_blocks(&self) -> Option<u64> {
+        self.decay_blocks
+    }
+
+    /// Reign length after which anyone may call `decay_champion`, `None`
+    /// disables decay.
+    pub fn set_decay_blocks(&mut self, decay_blocks: Option<u64>) {
+        self.assert_owner();
+        self.decay_blocks = decay_blocks;
+    }
+
+    /// Reverts a champion nobody dethroned for `decay_blocks` to the one it
+    /// replaced, keeping a stale game moving. The restored champion keeps its
+    /// own predecessor, so repeated decays walk further back instead of
+    /// bouncing between two words, and stop at the start of the season.
+    /// Permissionless once the window has passed, except during purchased
+    /// immunity.
+    pub fn decay_champion(&mut self) {
+        self.assert_paused();
+        self.assert_maintenance();
+        let decay_blocks = self.decay_blocks.expect("ERR_DECAY_DISABLED");
+        require!(
+            env::block_height() >= self.champion_since_block.saturating_add(decay_blocks),
+            "ERR_DECAY_TOO_EARLY"
+        );
+        require!(!self.is_champion_immune(), "ERR_CHAMPION_IMMUNE");
+
+        let previous = self
+            .current_record()
+            .and_then(|record| record.predecessor)
+            .and_then(|index| self.champion_history.get(index))
+            .expect("ERR_NO_PREVIOUS_CHAMPION");
+        let decayed = self.current_champion.clone();
+
+        require!(
+            self.replace_champion(
+                previous.champion,
+                previous.owner_id,
+                DECAY_REASON,
+                previous.predecessor,
+            ),
+            "ERR_NO_PREVIOUS_CHAMPION"
+        );
+        events::emit::champion_decayed(
+            self.event_forwarder.as_ref(),
+            &decayed,
+            &self.current_champion,
+        );
+    }
+
+    /// Champion changes crowned in the last `blocks` blocks. The initial
+    /// champion recorded by `new` does not count as a change.
+    pub fn get_recent_win_count(&self, blocks: u64) -> u64 {
+        let from_block = env::block_height().saturating_sub(blocks);
+        let first = std::cmp::max(self.first_record_at_or_after(from_block), 1);
+        self.champion_history.len().saturating_sub(first)
+    }
+
+    /// Adds up to `max` of `records` from an earlier deployment during a
+    /// migration, only while in maintenance. They are kept apart from the
+    /// on-chain history, in block order among themselves, and their words
+    /// join the past champions. The current champion is left alone. Returns
+    /// the number of records imported, the caller resubmits the rest to page
+    /// through a large import.
+    pub fn import_champions(&mut self, records: Vec<ChampionRecord>, max: u64) -> u64 {
+        self.assert_owner();
+        require!(self.maintenance, "ERR_NOT_IN_MAINTENANCE");
+
+        let records = &records[..records.len().min(max.min(MAX_IMPORT_BATCH) as usize)];
+        let last_block = self
+            .imported_champions
+            .len()
+            .checked_sub(1)
+            .and_then(|index| self.imported_champions.get(index))
+            .map_or(0, |record| record.block_height);
+        for record in self.check_champion_records(records, last_block) {
+            self.imported_champions.push(&record);
+        }
+
+        log!("Imported {} champion records", records.len());
+        records.len() as u64
+    }
+
+    /// Records added by `import_champions`, oldest first.
+    pub fn get_imported_champions(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<ChampionRecord> {
+        let from_index = from_index.unwrap_or(0);
+
+        (from_index
+            ..std::cmp::min(
+                from_index.saturating_add(page_limit(limit)),
+                self.imported_champions.len(),
+            ))
+            .filter_map(|index| self.imported_champions.get(index))
+            .collect()
+    }
+}
+
+impl Contract {
+    /// Appends `records` to the history after checking they continue it in
+    /// block order, each replacing the one before it. Does not touch the
+    /// current champion.
+    pub(crate) fn append_champion_records(&mut self, records: &[ChampionRecord]) {
+        let last_block = self
+            .current_record()
+            .map_or(0, |record| record.block_height);
+
+        for record in self.check_champion_records(records, last_block) {
+            self.champion_history.push(&ChampionRecord {
+                predecessor: self.champion_history.len().checked_sub(1),
+                ..record
+            });
+        }
+    }
+
+    /// Normalizes `records` after checking they follow `last_block` in block
+    /// order, and counts their words as past champions.
+    fn check_champion_records(
+        &mut self,
+        records: &[ChampionRecord],
+        mut last_block: BlockHeight,
+    ) -> Vec<ChampionRecord> {
+        records
+            .iter()
+            .map(|record| {
+                let champion = record.champion.to_lowercase();
+                assert!(is_valid_string(champion.as_str()), "Illegal input string");
+                require!(
+                    record.block_height >= last_block && record.block_height <= env::block_height(),
+                    "ERR_RECORD_OUT_OF_ORDER"
+                );
+                last_block = record.block_height;
+
+                self.all_champions.insert(&champion);
+                let reign_count = self.reign_counts.get(&champion).unwrap_or(0);
+                self.reign_counts.insert(&champion, &(reign_count + 1));
+                ChampionRecord {
+                    champion,
+                    predecessor: None,
+                    ..record.clone()
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn is_warming_up(&self) -> bool {
+        self.all_champions.len() < self.min_champions_to_start
+    }
+
+    /// Called right before the current champion is replaced.
+    pub(crate) fn end_reign(&mut self) {
+        let blocks = env::block_height() - self.champion_since_block;
+        self.total_reign_blocks += blocks;
+        self.completed_reigns += 1;
+
+        if self
+            .longest_reign
+            .as_ref()
+            .is_none_or(|longest| blocks > longest.blocks)
+        {
+            self.longest_reign = Some(Reign {
+                champion: self.current_champion.clone(),
+                owner_id: self.champion_owner.clone(),
+                blocks,
+            });
+        }
+    }
+
+    /// The last history record, which describes the current reign.
+    pub(crate) fn current_record(&self) -> Option<ChampionRecord> {
+        self.champion_history
+            .len()
+            .checked_sub(1)
+            .and_then(|index| self.champion_history.get(index))
+    }
+
+    pub(crate) fn record_champion(&mut self, predecessor: Option<u64>) {
+        let reign_count = self.reign_counts.get(&self.current_champion).unwrap_or(0);
+        self.reign_counts
+            .insert(&self.current_champion, &(reign_count + 1));
+
+        self.champion_history.push(&ChampionRecord {
+            champion: self.current_champion.clone(),
+            owner_id: self.champion_owner.clone(),
+            block_height: env::block_height(),
+            predecessor,
+        });
+    }
+
+    /// Credits the current champion with dethroning `dethroned`. A word
+    /// handed to a new owner, or restored by decay, has not beaten anything.
+    pub(crate) fn record_conquest(&mut self, dethroned: String) {
+        if dethroned == self.current_champion {
+            return;
+        }
+        let mut conquests = self
+            .conquests
+            .get(&self.current_champion)
+            .unwrap_or_else(|| {
+                Vector::new(StorageKey::ConquestsInner {
+                    word_hash: env::sha256_array(self.current_champion.as_bytes()),
+                })
+            });
+        conquests.push(&dethroned);
+        self.conquests.insert(&self.current_champion, &conquests);
+    }
+
+    /// History is appended in block order, so a binary search finds the start.
+    fn first_record_at_or_after(&self, block_height: BlockHeight) -> u64 {
+        let (mut low, mut high) = (0, self.champion_history.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.champion_history.get(middle).unwrap().block_height < block_height {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+}
@@ -0,0 +1,65 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// Last block of the current champion's purchased immunity, during which
+    /// no new challenges are accepted. Ends as soon as the champion changes.
+    pub fn get_immune_until_block(&self) -> BlockHeight {
+        self.immune_until_block
+    }
+
+    pub fn get_immunity_config(&self) -> (NearToken, u64) {
+        (self.immunity_price_per_block, self.max_immunity_blocks)
+    }
+
+    /// `price_per_block` of zero disables purchases. `max_blocks` caps the
+    /// total immunity of a reign, counted from the block the champion was
+    /// crowned, so repeat purchases cannot keep the champion immune forever.
+    pub fn set_immunity_config(&mut self, price_per_block: NearToken, max_blocks: u64) {
+        self.assert_owner();
+        self.immunity_price_per_block = price_per_block;
+        self.max_immunity_blocks = max_blocks;
+    }
+
+    /// Lets the champion owner buy one block of immunity per
+    /// `immunity_price_per_block` attached, on top of any immunity left. The
+    /// whole deposit goes to the prize pool. Returns the new last immune block.
+    #[payable]
+    pub fn extend_immunity(&mut self) -> BlockHeight {
+        self.assert_maintenance();
+        require!(
+            env::predecessor_account_id() == self.champion_owner,
+            "ERR_NOT_CHAMPION_OWNER"
+        );
+        require!(
+            !self.immunity_price_per_block.is_zero(),
+            "ERR_IMMUNITY_DISABLED"
+        );
+
+        let deposit = env::attached_deposit();
+        let blocks: u64 = (deposit.as_yoctonear() / self.immunity_price_per_block.as_yoctonear())
+            .try_into()
+            .unwrap_or(u64::MAX);
+        require!(blocks > 0, "ERR_DEPOSIT_TOO_LOW");
+
+        let immune_until =
+            std::cmp::max(self.immune_until_block, env::block_height()).saturating_add(blocks);
+        require!(
+            immune_until
+                <= self
+                    .champion_since_block
+                    .saturating_add(self.max_immunity_blocks),
+            "ERR_IMMUNITY_CAP"
+        );
+
+        self.immune_until_block = immune_until;
+        self.prize_pool = self.prize_pool.saturating_add(deposit);
+        immune_until
+    }
+}
+
+impl Contract {
+    pub(crate) fn is_champion_immune(&self) -> bool {
+        env::block_height() <= self.immune_until_block
+    }
+}
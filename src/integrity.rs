@@ -0,0 +1,106 @@
+use crate::*;
+use std::collections::HashSet;
+
+/// Offending request ids listed per kind of problem, the counts are complete.
+const MAX_INTEGRITY_SAMPLE: usize = 10;
+
+#[derive(Serialize, JsonSchema, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntegrityIssue {
+    pub count: u64,
+    pub sample: Vec<RequestId>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StateIntegrityReport {
+    /// First request id after the scanned range, where the next page starts.
+    pub next_request_id: RequestId,
+    /// Stored responses whose request is gone.
+    pub orphan_responses: IntegrityIssue,
+    /// Open requests past their response deadline.
+    pub expired_requests: IntegrityIssue,
+    /// Open requests sharing a yield `data_id` with an earlier one in the range.
+    pub duplicate_data_ids: IntegrityIssue,
+}
+
+impl IntegrityIssue {
+    fn report(&mut self, request_id: RequestId) {
+        self.count += 1;
+        if self.sample.len() < MAX_INTEGRITY_SAMPLE {
+            self.sample.push(request_id);
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Checks up to `limit` request ids starting at `from_request_id` for
+    /// inconsistent state. Page through all ids with `next_request_id`;
+    /// duplicate data ids are only detected within one page.
+    pub fn verify_state_integrity(
+        &self,
+        from_request_id: Option<RequestId>,
+        limit: Option<u64>,
+    ) -> StateIntegrityReport {
+        let from_request_id = from_request_id.unwrap_or(0);
+        let to_request_id = std::cmp::min(
+            from_request_id.saturating_add(page_limit(limit)),
+            self.num_requests,
+        );
+
+        let mut report = StateIntegrityReport {
+            next_request_id: std::cmp::max(from_request_id, to_request_id),
+            ..Default::default()
+        };
+        let mut data_ids = HashSet::new();
+
+        for request_id in from_request_id..to_request_id {
+            match self.requests.get(&request_id) {
+                Some(request) => {
+                    if self.is_expired(&request) {
+                        report.expired_requests.report(request_id);
+                    }
+                    if !data_ids.insert(request.data_id) {
+                        report.duplicate_data_ids.report(request_id);
+                    }
+                }
+                None => {
+                    if self.responses.contains_key(&request_id) {
+                        report.orphan_responses.report(request_id);
+                    }
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Checksum of one page of state for replicas to compare with their
+    /// copy. The page covers indices `from_index..from_index + limit` of both
+    /// the champion set, in first-crowned order, and the request ids. The
+    /// result is the base58 `sha256` of the borsh encoding of
+    /// `(Vec<(u64, String)>, Vec<(RequestId, Request)>)`: the champions at
+    /// those indices, then the still-open requests with those ids, each in
+    /// ascending order. Identical state always yields the same hash.
+    pub fn get_state_snapshot_hash(&self, from_index: Option<u64>, limit: Option<u64>) -> String {
+        let from_index = from_index.unwrap_or(0);
+        let to_index = from_index.saturating_add(page_limit(limit));
+
+        let champions = self.all_champions.as_vector();
+        let champions: Vec<(u64, String)> = (from_index..std::cmp::min(to_index, champions.len()))
+            .filter_map(|index| champions.get(index).map(|champion| (index, champion)))
+            .collect();
+        let requests: Vec<(RequestId, Request)> = (from_index
+            ..std::cmp::min(to_index, self.num_requests))
+            .filter_map(|request_id| {
+                self.requests
+                    .get(&request_id)
+                    .map(|request| (request_id, request))
+            })
+            .collect();
+
+        let bytes = near_sdk::borsh::to_vec(&(champions, requests)).unwrap();
+        near_sdk::bs58::encode(env::sha256_array(&bytes)).into_string()
+    }
+}
@@ -1,20 +1,81 @@
+use near_sdk::base64::Engine;
 use near_sdk::collections::UnorderedSet;
 use near_sdk::{
     borsh::{BorshDeserialize, BorshSerialize},
-    collections::{LookupMap, UnorderedMap},
+    collections::{LookupMap, UnorderedMap, Vector},
     env, log, near_bindgen, require,
     serde::{Deserialize, Serialize},
-    AccountId, BorshStorageKey, Gas, GasWeight, PanicOnDefault, PromiseOrValue,
+    AccountId, BlockHeight, BorshStorageKey, CurveType, Gas, GasWeight, NearToken, PanicOnDefault,
+    PromiseIndex, PromiseOrValue, PublicKey,
 };
 use schemars::JsonSchema;
+use std::collections::{BTreeMap, VecDeque};
 use std::convert::TryInto;
 
+// Gas split of `request`: the yield reserves exactly `yield_callback_gas`, at
+// least `MIN_RESPONSE_GAS`, for `await_response`, plus the trophy mint gas
+// while trophies are enabled, and with `GasWeight(0)` receives none of the
+// unused gas. `MIN_REQUEST_GAS` must remain for the body
+// of `request` itself. Anything attached on top is never forwarded and is
+// refunded to the caller.
 const MIN_REQUEST_GAS: Gas = Gas::from_tgas(40);
 const MIN_RESPONSE_GAS: Gas = Gas::from_tgas(40);
+const MAX_RESPONSE_GAS: Gas = Gas::from_tgas(200);
+/// Gas the operator should attach to `respond`: validation, signature check and resume.
+const RESPOND_GAS: Gas = Gas::from_tgas(20);
 const DATA_ID_REGISTER: u64 = 0;
+const MAX_INITIAL_CHAMPIONS: usize = 100;
+const MAX_SIMILARITY_SCAN: u64 = 500;
+const DEFAULT_PAGE_LIMIT: u64 = 50;
+/// Hard cap on the page size of every paged view, larger limits are clamped.
+const MAX_VIEW_LIMIT: u64 = 200;
+const MAX_PROVENANCE_FIELD_LEN: usize = 128;
+const MAX_TAGS: usize = 5;
+const MAX_CONTENT_REFERENCE_LEN: usize = 256;
+const MAX_TAG_LEN: usize = 32;
+/// Ruling reason of guesses resolved from `known_answers`.
+const KNOWN_ANSWER_REASON: &str = "known answer";
+mod agents;
+mod audit;
+mod bans;
+mod bracket;
+mod claims;
+mod config;
+mod cooldowns;
+mod deadlines;
+mod escrow;
 mod events;
+mod flags;
+mod health;
+mod history;
+mod immunity;
+mod integrity;
+mod merkle;
+mod migration;
+mod prompts;
+mod queue;
+mod recent_guesses;
+mod season;
+mod seed;
+mod stats;
+mod ties;
+mod timeline;
+mod trophies;
 mod utils;
 
+use crate::agents::*;
+use crate::audit::*;
+use crate::bracket::*;
+use crate::claims::*;
+use crate::deadlines::*;
+use crate::flags::*;
+use crate::history::*;
+use crate::migration::*;
+use crate::prompts::*;
+use crate::queue::*;
+use crate::stats::*;
+use crate::ties::*;
+use crate::timeline::*;
 use crate::utils::*;
 
 pub type CryptoHash = [u8; 32];
@@ -27,6 +88,51 @@ pub struct Request {
     #[schemars(with = "String")]
     originator_id: AccountId,
     message: String,
+    agent: String,
+    provenance: Option<RequestSignature>,
+    created_block: BlockHeight,
+    champion_at_request: String,
+    /// Unpredictable per-request value the agent must echo in its response.
+    challenge: String,
+    /// Player language tag so the agent can reply in it, the champion word stays normalized.
+    locale: Option<String>,
+    /// Set by `ack_request` once the agent has picked the request up.
+    acked: bool,
+    /// Player supplied categories of the guess, lowercase, for analytics.
+    tags: Vec<String>,
+    /// Blocks added to the response deadline by `extend_deadline`.
+    deadline_extension: u64,
+    /// Set for bracket matches, whose defender replaces the champion.
+    bracket_match: Option<BracketMatch>,
+}
+
+/// A validated submission before it becomes an open `Request`, as stored by
+/// the overload queue.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct NewRequest {
+    #[schemars(with = "String")]
+    originator_id: AccountId,
+    message: String,
+    agent: String,
+    provenance: Option<RequestSignature>,
+    locale: Option<String>,
+    tags: Vec<String>,
+    #[schemars(with = "String")]
+    deposit: NearToken,
+    bracket_match: Option<BracketMatch>,
+}
+
+/// Player signature over the borsh encoding of `(message, nonce)`, where
+/// `message` is the lowercased guess as stored in the `Request`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct RequestSignature {
+    public_key: String,
+    nonce: u64,
+    signature: String,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
@@ -36,6 +142,14 @@ pub struct Response {
     pub ok: bool,
     pub data: Option<String>,
     pub signature: Option<String>,
+    /// Echo of `Request::challenge`, covered by the signature.
+    #[serde(default)]
+    pub challenge: Option<String>,
+    /// Originator of the request the agent believes it answers. Not signed,
+    /// only a guard against responses routed to the wrong request.
+    #[serde(default)]
+    #[schemars(with = "Option<String>")]
+    pub originator: Option<AccountId>,
 }
 
 #[derive(Deserialize)]
@@ -44,7 +158,17 @@ pub struct Response {
 pub struct ResponseMsg {
     current_champion: String,
     guess_wins: bool,
+    /// Inline explanation, or with `data_is_reference` an `ipfs://` or
+    /// `ar://` reference to the full agent output stored off-chain.
     reason: String,
+    #[serde(default)]
+    data_is_reference: bool,
+    /// Canonical form of the winning guess to crown instead of the raw message.
+    #[serde(default)]
+    canonical_champion: Option<String>,
+    /// Added to the player's `PlayerStats`, at most `max_bonus_points`.
+    #[serde(default)]
+    bonus_points: u64,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
@@ -54,6 +178,27 @@ pub struct AgentData {
     request: Request,
     champions: Vec<String>,
     prompt: String,
+    response_deadline_block: BlockHeight,
+    locale: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageStats {
+    bytes: u64,
+    #[schemars(with = "String")]
+    staked: NearToken,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CycleGas {
+    #[schemars(with = "String")]
+    request_gas: Gas,
+    #[schemars(with = "String")]
+    respond_gas: Gas,
+    #[schemars(with = "String")]
+    total: Gas,
 }
 
 pub type RequestId = u64;
@@ -64,19 +209,148 @@ pub type RequestId = u64;
 pub struct Contract {
     agent_name: String,
     agent_public_key: String,
+    agent_key_type: KeyType,
     agent_system_prompt: String,
+    min_blocks_between_prompt_changes: u64,
+    last_prompt_change_block: BlockHeight,
+    agents: UnorderedMap<String, AgentConfig>,
+    /// Open and queued requests per agent name.
+    agent_requests: LookupMap<String, u64>,
+    /// Primary agent prompt overrides while a given word is champion.
+    champion_prompts: LookupMap<String, String>,
+    prompt_segments: LookupMap<PromptSegment, String>,
+    /// `get_question` wording with a `{champion}` placeholder.
+    question_template: Option<String>,
+    /// Incremented by every `start_new_season`, the first season is 0.
+    season: u64,
 
     paused: bool,
+    maintenance: bool,
+    consecutive_agent_errors: u64,
+    max_consecutive_agent_errors: Option<u64>,
+    feature_flags: FeatureFlags,
     requests: UnorderedMap<RequestId, Request>,
     responses: LookupMap<RequestId, Response>,
+    /// Why the agent ruling for a request could not be applied.
+    request_errors: LookupMap<RequestId, String>,
+    account_requests: LookupMap<AccountId, UnorderedSet<RequestId>>,
+    /// Open requests per guess, for the `reject_duplicate_in_flight` flag.
+    in_flight_guesses: LookupMap<String, u64>,
+    /// Latest guesses per account, oldest first, for `reject_repeat_guesses`.
+    recent_guesses: LookupMap<AccountId, VecDeque<String>>,
+    repeat_guess_window: u64,
     num_requests: u64,
+    /// Requests beyond this many open ones are rejected or queued.
+    max_open_requests: Option<u64>,
+    queued_requests: LookupMap<u64, NewRequest>,
+    /// Requests beyond this many queued ones are rejected, the queue holds
+    /// no yield and needs no deposit, so it must not grow without bound.
+    max_queue_length: u64,
+    queue_head: u64,
+    queue_tail: u64,
+    /// Positions between `queue_head` and `queue_tail` cancelled by their player.
+    queue_cancelled: u64,
+    /// Block at which a worker claimed an open request.
+    claims: LookupMap<RequestId, BlockHeight>,
+    claim_lease_blocks: u64,
+    tie_break: TieBreak,
+    /// Submitted winning rulings not yet resolved, per champion they beat.
+    pending_wins: LookupMap<String, Vec<RequestId>>,
+    oldest_open_request: RequestId,
+    response_deadline_blocks: u64,
+    timelines: LookupMap<RequestId, RequestTimeline>,
+    /// Deposits of open requests, settled when they resolve.
+    escrows: LookupMap<RequestId, NearToken>,
+    /// Part of the deposit covering the storage of an open request.
+    storage_stakes: LookupMap<RequestId, NearToken>,
+    min_deposit: NearToken,
+    win_fee_bps: u32,
+    prize_pool: NearToken,
+    total_collected: NearToken,
+    /// Lifetime request deposits per account, checked against
+    /// `max_total_deposit`.
+    account_deposits: LookupMap<AccountId, NearToken>,
+    max_total_deposit: Option<NearToken>,
+    win_reward_base: NearToken,
+    /// Added to the win reward per block the dethroned champion reigned.
+    win_reward_per_block: NearToken,
+    /// Paid from the prize pool to whoever calls `force_timeout`.
+    keeper_reward: NearToken,
+    timeout_response: Response,
+    /// Prepaid gas `request` demands before doing any work.
+    min_prepaid_gas: Gas,
+    /// Gas reserved for `await_response` by every yield.
+    yield_callback_gas: Gas,
 
     owner_id: AccountId,
     operator_id: AccountId,
 
     current_champion: String,
     champion_owner: AccountId,
+    champion_since_block: BlockHeight,
+    /// Why the current champion was crowned, `None` for the initial one.
+    champion_reason: Option<String>,
+    /// Times each word has been crowned.
+    reign_counts: LookupMap<String, u64>,
+    /// Words each champion dethroned, in order.
+    conquests: LookupMap<String, Vector<String>>,
+    longest_reign: Option<Reign>,
+    total_reign_blocks: u64,
+    completed_reigns: u64,
     all_champions: UnorderedSet<String>,
+    champion_history: Vector<ChampionRecord>,
+    /// History of an earlier deployment added by `import_champions`.
+    imported_champions: Vector<ChampionRecord>,
+    immune_until_block: BlockHeight,
+    immunity_price_per_block: NearToken,
+    max_immunity_blocks: u64,
+    decay_blocks: Option<u64>,
+    bracket: Option<Bracket>,
+    /// Set once `seed_game` has run.
+    seeded: bool,
+    /// Crowning this word ends the game by pausing the contract.
+    target_champion: Option<String>,
+    min_champion_len: u32,
+    crowned_cooldown_blocks: u64,
+    /// Words crowned within `crowned_cooldown_blocks`, with the block their
+    /// cooldown ends at.
+    recently_crowned: VecDeque<(String, BlockHeight)>,
+    /// Wins only add to `all_champions` until it holds this many words.
+    min_champions_to_start: u64,
+
+    known_answers: LookupMap<String, bool>,
+    /// Accounts `request` rejects.
+    banned_accounts: UnorderedSet<AccountId>,
+    /// Next expected provenance nonce per account.
+    nonces: LookupMap<AccountId, u64>,
+    /// Rulings keyed by `(challenger, champion)`, known answers included.
+    matchups: LookupMap<(String, String), bool>,
+
+    trophy_nft_contract: Option<AccountId>,
+    trophy_mint_deposit: NearToken,
+    /// Trophies minted so far, the next token id.
+    num_trophies: u64,
+
+    event_forwarder: Option<AccountId>,
+
+    recent_outcomes: VecDeque<bool>,
+    total_wins: u64,
+    total_losses: u64,
+    /// Agent responses per `get_latency_histogram` bucket.
+    latency_counts: Vec<u64>,
+    player_stats: UnorderedMap<AccountId, PlayerStats>,
+    /// Agent rulings against each word while it was champion.
+    champion_stats: LookupMap<String, ChampionStats>,
+    dominance_weights: DominanceWeights,
+    max_bonus_points: u64,
+    /// Accounts that challenged each word while it was champion.
+    challengers: LookupMap<String, UnorderedSet<AccountId>>,
+
+    responses_log: Vector<StoredResponse>,
+    /// Indices into `responses_log` per originator.
+    account_log: LookupMap<AccountId, Vector<u64>>,
+    /// Index into `responses_log` per resolved request.
+    log_indices: LookupMap<RequestId, u64>,
 }
 
 #[derive(BorshSerialize, BorshStorageKey)]
@@ -85,48 +359,257 @@ enum StorageKey {
     Requests,
     Responses,
     AllChampions,
+    ChampionHistory,
+    Agents,
+    AgentRequests,
+    KnownAnswers,
+    Matchups,
+    Nonces,
+    AccountRequests,
+    AccountRequestsInner { account_hash: CryptoHash },
+    RequestErrors,
+    QueuedRequests,
+    PlayerStats,
+    PendingWins,
+    ResponsesLog,
+    AccountLog,
+    AccountLogInner { account_hash: CryptoHash },
+    ChampionPrompts,
+    Claims,
+    ImportedChampions,
+    ChampionStats,
+    Timelines,
+    Challengers,
+    ChallengersInner { word_hash: CryptoHash },
+    Escrows,
+    PromptSegments,
+    StorageStakes,
+    ReignCounts,
+    InFlightGuesses,
+    RecentGuesses,
+    Conquests,
+    ConquestsInner { word_hash: CryptoHash },
+    AccountDeposits,
+    BannedAccounts,
+    LogIndices,
 }
 
 #[near_bindgen]
 impl Contract {
+    /// `initial_champion` is owned by `initial_champion_owner`, or by the
+    /// contract account itself when omitted, so the first real winner is the
+    /// first player to own a champion.
     #[init]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         owner_id: AccountId,
         operator_id: AccountId,
 
         initial_champion: String,
+        initial_champion_owner: Option<AccountId>,
+        initial_champions: Option<Vec<String>>,
 
         agent_name: String,
         agent_system_prompt: String,
         agent_public_key: String,
+        agent_key_type: Option<KeyType>,
     ) -> Self {
+        let agent_key_type = agent_key_type.unwrap_or_default();
+        require!(
+            is_valid_public_key(&agent_public_key, agent_key_type),
+            "ERR_INVALID_PUBLIC_KEY"
+        );
+
         let mut all_champions = UnorderedSet::new(StorageKey::AllChampions);
         all_champions.insert(&initial_champion);
 
-        Self {
+        let initial_champions = initial_champions.unwrap_or_default();
+        require!(
+            initial_champions.len() <= MAX_INITIAL_CHAMPIONS,
+            "ERR_TOO_MANY_INITIAL_CHAMPIONS"
+        );
+        for champion in initial_champions {
+            let champion = champion.to_lowercase();
+            assert!(is_valid_string(champion.as_str()), "Illegal input string");
+            all_champions.insert(&champion);
+        }
+
+        let mut contract = Self {
             agent_name,
             agent_public_key,
+            agent_key_type,
             agent_system_prompt,
+            min_blocks_between_prompt_changes: 0,
+            last_prompt_change_block: env::block_height(),
+            agents: UnorderedMap::new(StorageKey::Agents),
+            agent_requests: LookupMap::new(StorageKey::AgentRequests),
+            champion_prompts: LookupMap::new(StorageKey::ChampionPrompts),
+            prompt_segments: LookupMap::new(StorageKey::PromptSegments),
+            question_template: None,
+            season: 0,
 
-            owner_id: owner_id.clone(),
+            owner_id,
             operator_id,
 
             paused: false,
+            maintenance: false,
+            consecutive_agent_errors: 0,
+            max_consecutive_agent_errors: None,
+            feature_flags: FeatureFlags::default(),
 
             requests: UnorderedMap::new(StorageKey::Requests),
             responses: LookupMap::new(StorageKey::Responses),
+            request_errors: LookupMap::new(StorageKey::RequestErrors),
+            account_requests: LookupMap::new(StorageKey::AccountRequests),
+            in_flight_guesses: LookupMap::new(StorageKey::InFlightGuesses),
+            recent_guesses: LookupMap::new(StorageKey::RecentGuesses),
+            repeat_guess_window: 0,
             num_requests: 0,
+            max_open_requests: None,
+            queued_requests: LookupMap::new(StorageKey::QueuedRequests),
+            max_queue_length: DEFAULT_MAX_QUEUE_LENGTH,
+            queue_head: 0,
+            queue_tail: 0,
+            queue_cancelled: 0,
+            claims: LookupMap::new(StorageKey::Claims),
+            claim_lease_blocks: DEFAULT_CLAIM_LEASE_BLOCKS,
+            tie_break: TieBreak::default(),
+            pending_wins: LookupMap::new(StorageKey::PendingWins),
+            oldest_open_request: 0,
+            min_prepaid_gas: MIN_REQUEST_GAS.saturating_add(MIN_RESPONSE_GAS),
+            yield_callback_gas: MIN_RESPONSE_GAS,
+            response_deadline_blocks: DEFAULT_RESPONSE_DEADLINE_BLOCKS,
+            timelines: LookupMap::new(StorageKey::Timelines),
+            escrows: LookupMap::new(StorageKey::Escrows),
+            storage_stakes: LookupMap::new(StorageKey::StorageStakes),
+            min_deposit: NearToken::from_yoctonear(0),
+            win_fee_bps: 0,
+            prize_pool: NearToken::from_yoctonear(0),
+            total_collected: NearToken::from_yoctonear(0),
+            account_deposits: LookupMap::new(StorageKey::AccountDeposits),
+            max_total_deposit: None,
+            win_reward_base: NearToken::from_yoctonear(0),
+            win_reward_per_block: NearToken::from_yoctonear(0),
+            keeper_reward: NearToken::from_yoctonear(0),
+            timeout_response: default_timeout_response(),
 
             current_champion: initial_champion.to_string(),
-            champion_owner: owner_id,
+            champion_owner: initial_champion_owner.unwrap_or_else(env::current_account_id),
+            champion_since_block: env::block_height(),
+            champion_reason: None,
+            reign_counts: LookupMap::new(StorageKey::ReignCounts),
+            conquests: LookupMap::new(StorageKey::Conquests),
+            longest_reign: None,
+            total_reign_blocks: 0,
+            completed_reigns: 0,
             all_champions,
-        }
+            champion_history: Vector::new(StorageKey::ChampionHistory),
+            imported_champions: Vector::new(StorageKey::ImportedChampions),
+            immune_until_block: 0,
+            immunity_price_per_block: NearToken::from_yoctonear(0),
+            max_immunity_blocks: 0,
+            decay_blocks: None,
+            bracket: None,
+            seeded: false,
+            target_champion: None,
+            min_champion_len: 1,
+            min_champions_to_start: 0,
+            crowned_cooldown_blocks: 0,
+            recently_crowned: VecDeque::new(),
+
+            known_answers: LookupMap::new(StorageKey::KnownAnswers),
+            matchups: LookupMap::new(StorageKey::Matchups),
+            banned_accounts: UnorderedSet::new(StorageKey::BannedAccounts),
+            nonces: LookupMap::new(StorageKey::Nonces),
+
+            trophy_nft_contract: None,
+            trophy_mint_deposit: NearToken::from_yoctonear(0),
+            num_trophies: 0,
+
+            event_forwarder: None,
+
+            recent_outcomes: VecDeque::new(),
+            total_wins: 0,
+            total_losses: 0,
+            latency_counts: vec![0; LATENCY_BUCKETS.len()],
+            player_stats: UnorderedMap::new(StorageKey::PlayerStats),
+            dominance_weights: DominanceWeights::default(),
+            max_bonus_points: 0,
+            champion_stats: LookupMap::new(StorageKey::ChampionStats),
+            challengers: LookupMap::new(StorageKey::Challengers),
+
+            responses_log: Vector::new(StorageKey::ResponsesLog),
+            account_log: LookupMap::new(StorageKey::AccountLog),
+            log_indices: LookupMap::new(StorageKey::LogIndices),
+        };
+
+        contract.record_champion(None);
+        write_state_version();
+        contract
     }
 
     pub fn get_all_champions(&self) -> Vec<String> {
         self.all_champions.to_vec()
     }
 
+    /// Page of the champion set in alphabetical order. Every call reads and
+    /// sorts the whole set, so it is refused past `MAX_MERKLE_CHAMPIONS`
+    /// words; export the set with `export_champions_encoded` beyond that.
+    pub fn get_champions_sorted(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<String> {
+        self.sorted_champions()
+            .into_iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(page_limit(limit) as usize)
+            .collect()
+    }
+
+    /// Full champion history for off-chain export, cheaper than paging JSON.
+    ///
+    /// Format: standard base64 (with padding) of the borsh encoding of
+    /// `Vec<String>`, i.e. a little-endian `u32` count followed by each word
+    /// as a little-endian `u32` byte length and its UTF-8 bytes. Order matches
+    /// `get_all_champions`.
+    pub fn export_champions_encoded(&self) -> String {
+        let champions = near_sdk::borsh::to_vec(&self.all_champions.to_vec())
+            .expect("Failed to serialize champions");
+        near_sdk::base64::engine::general_purpose::STANDARD.encode(champions)
+    }
+
+    /// Closest past champion within `max_distance` edits of `word`. Only the
+    /// first `MAX_SIMILARITY_SCAN` champions are compared to bound gas.
+    pub fn is_similar_to_past_champion(&self, word: String, max_distance: u32) -> Option<String> {
+        let word = word.to_lowercase();
+
+        self.all_champions
+            .as_vector()
+            .iter()
+            .take(MAX_SIMILARITY_SCAN as usize)
+            .map(|champion| (levenshtein(&word, &champion), champion))
+            .filter(|(distance, _)| *distance <= max_distance as usize)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, champion)| champion)
+    }
+
+    /// Page of past champions starting with `prefix`, in the order of
+    /// `get_all_champions`; `from_index` counts matches, not champions. This
+    /// is a linear scan that stops once the page is full, so gas grows with
+    /// the set size for rare prefixes and late pages.
+    pub fn search_champions(
+        &self,
+        prefix: String,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<String> {
+        let prefix = prefix.to_lowercase();
+
+        self.all_champions
+            .iter()
+            .filter(|champion| champion.starts_with(&prefix))
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(page_limit(limit) as usize)
+            .collect()
+    }
+
     pub fn get_champion(&self) -> String {
         self.current_champion.clone()
     }
@@ -135,49 +618,788 @@ impl Contract {
         self.champion_owner.clone()
     }
 
+    /// Whether a previous ruling found that `challenger` beats `champion`.
+    pub fn get_matchup(&self, challenger: String, champion: String) -> Option<bool> {
+        self.matchups
+            .get(&(challenger.to_lowercase(), champion.to_lowercase()))
+    }
+
+    pub fn get_target_champion(&self) -> Option<String> {
+        self.target_champion.clone()
+    }
+
+    pub fn set_target_champion(&mut self, target_champion: Option<String>) {
+        self.assert_owner();
+        self.target_champion = target_champion.map(|word| word.to_lowercase());
+    }
+
+    pub fn get_min_champion_len(&self) -> u32 {
+        self.min_champion_len
+    }
+
+    pub fn set_min_champion_len(&mut self, min_champion_len: u32) {
+        self.assert_owner();
+        self.min_champion_len = min_champion_len;
+    }
+
+    /// Id the next opened request will get. Only a hint: other requests
+    /// landing first, or the request being queued until `promote_queued`,
+    /// change the id it actually receives.
+    pub fn get_next_request_id(&self) -> RequestId {
+        self.num_requests
+    }
+
     pub fn get_request(&self, request_id: RequestId) -> Request {
         self.requests.get(&request_id).unwrap()
     }
 
+    /// Borsh bytes of the stored `Request` for diagnosing off-chain decoders.
+    /// This is a view: views have no predecessor to gate on, and the same
+    /// bytes are readable from contract state anyway.
+    pub fn debug_request_bytes(&self, request_id: RequestId) -> Vec<u8> {
+        near_sdk::borsh::to_vec(&self.get_request(request_id)).expect("Failed to serialize request")
+    }
+
+    /// Nonce the next signed request of `account_id` must carry.
+    pub fn get_nonce(&self, account_id: AccountId) -> u64 {
+        self.nonces.get(&account_id).unwrap_or(0)
+    }
+
+    pub fn get_request_error(&self, request_id: RequestId) -> Option<String> {
+        self.request_errors.get(&request_id)
+    }
+
     pub fn get_question(&self) -> String {
-        format!("What beats {}?", self.current_champion)
+        self.question_for(&self.current_champion)
     }
 
     pub fn get_requests(&self) -> Vec<(RequestId, Request)> {
         self.requests.iter().collect()
     }
 
+    /// Ids of open requests only, for pollers that diff which requests are
+    /// still pending. Uses the same ordering as `get_requests`.
+    pub fn get_open_request_ids(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<RequestId> {
+        let keys = self.requests.keys_as_vector();
+        let from_index = from_index.unwrap_or(0);
+        let limit = page_limit(limit);
+
+        (from_index..std::cmp::min(from_index.saturating_add(limit), keys.len()))
+            .filter_map(|index| keys.get(index))
+            .collect()
+    }
+
+    /// Open request ids grouped by originator, accounts with the most open
+    /// requests first. Every call reads all open requests before paging, so
+    /// view gas grows with the open request count like `get_requests`.
+    pub fn get_pending_by_originator(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<(AccountId, Vec<RequestId>)> {
+        let mut pending: BTreeMap<AccountId, Vec<RequestId>> = BTreeMap::new();
+        for (request_id, request) in self.requests.iter() {
+            pending
+                .entry(request.originator_id)
+                .or_default()
+                .push(request_id);
+        }
+
+        let mut pending: Vec<(AccountId, Vec<RequestId>)> = pending.into_iter().collect();
+        pending.sort_by_key(|(_, request_ids)| std::cmp::Reverse(request_ids.len()));
+
+        pending
+            .into_iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(page_limit(limit) as usize)
+            .collect()
+    }
+
     pub fn agent_data(&self, request_id: RequestId) -> AgentData {
+        let request = self.get_request(request_id);
+        let prompt = self.request_prompt(&request);
+
         AgentData {
-            request: self.get_request(request_id),
+            response_deadline_block: self.deadline_block(&request),
+            locale: request.locale.clone(),
+            request,
             champions: self.get_all_champions(),
-            prompt: self.agent_system_prompt.clone(),
+            prompt,
+        }
+    }
+
+    pub fn get_storage_stats(&self) -> StorageStats {
+        let bytes = env::storage_usage();
+        StorageStats {
+            bytes,
+            staked: env::storage_byte_cost().saturating_mul(bytes as u128),
         }
     }
 
+    pub fn get_event_forwarder(&self) -> Option<AccountId> {
+        self.event_forwarder.clone()
+    }
+
+    /// Mirrors every emitted event to `on_event(event_json)` on this account.
+    pub fn set_event_forwarder(&mut self, event_forwarder: Option<AccountId>) {
+        self.assert_owner();
+        self.event_forwarder = event_forwarder;
+    }
+
+    /// Gas to attach across one request→response cycle: the player's
+    /// `request` (including the gas reserved for `await_response`) and the
+    /// operator's `respond`. Based on the configured minimums.
+    pub fn estimate_cycle_gas(&self) -> CycleGas {
+        let request_gas = self.min_prepaid_gas;
+        CycleGas {
+            request_gas,
+            respond_gas: RESPOND_GAS,
+            total: request_gas.saturating_add(RESPOND_GAS),
+        }
+    }
+
+    pub fn get_yield_callback_gas(&self) -> Gas {
+        self.yield_callback_gas
+    }
+
+    /// Between `MIN_RESPONSE_GAS` and `MAX_RESPONSE_GAS`.
+    pub fn set_yield_callback_gas(&mut self, yield_callback_gas: Gas) {
+        self.assert_owner();
+        self.set_yield_callback_gas_checked(yield_callback_gas);
+    }
+
+    pub fn get_min_prepaid_gas(&self) -> Gas {
+        self.min_prepaid_gas
+    }
+
+    /// Cannot go below what the yield and the body of `request` need anyway.
+    pub fn set_min_prepaid_gas(&mut self, min_prepaid_gas: Gas) {
+        self.assert_owner();
+        self.set_min_prepaid_gas_checked(min_prepaid_gas);
+    }
+
+    /// Whether `request` currently accepts guesses as far as contract-wide
+    /// state goes: not paused, not in maintenance and the primary agent fully
+    /// configured. Per-call checks such as gas or deposit still apply.
+    pub fn is_ready(&self) -> bool {
+        !self.paused
+            && !self.maintenance
+            && !self.agent_name.is_empty()
+            && !self.agent_public_key.is_empty()
+            && !self.agent_system_prompt.is_empty()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Also lifts a pause caused by crowning the target champion or by agent errors.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.assert_owner();
+        self.paused = paused;
+        self.consecutive_agent_errors = 0;
+    }
+
+    pub fn is_maintenance(&self) -> bool {
+        self.maintenance
+    }
+
+    pub fn set_maintenance(&mut self, maintenance: bool) {
+        self.assert_owner();
+        self.maintenance = maintenance;
+    }
+
+    /// The owner may change the prompt at any time, the operator only once
+    /// `min_blocks_between_prompt_changes` have passed since the last change.
     pub fn set_system_prompt(&mut self, prompt: String) {
-        self.assert_operator();
+        if env::predecessor_account_id() != self.owner_id {
+            self.assert_operator();
+            require!(
+                self.prompt_change_cooldown() == 0,
+                "ERR_PROMPT_CHANGE_TOO_SOON"
+            );
+        }
         self.agent_system_prompt = prompt;
+        self.last_prompt_change_block = env::block_height();
+    }
+
+    pub fn get_min_blocks_between_prompt_changes(&self) -> u64 {
+        self.min_blocks_between_prompt_changes
+    }
+
+    pub fn set_min_blocks_between_prompt_changes(&mut self, blocks: u64) {
+        self.assert_owner();
+        self.min_blocks_between_prompt_changes = blocks;
+    }
+
+    /// Known answers resolve `request` immediately without involving the agent.
+    pub fn set_known_answer(&mut self, word: String, wins: bool) {
+        self.assert_owner();
+        let word = word.to_lowercase();
+        assert!(is_valid_string(word.as_str()), "Illegal input string");
+        self.known_answers.insert(&word, &wins);
     }
 
-    pub fn request(&mut self, message: String) {
+    pub fn remove_known_answer(&mut self, word: String) {
+        self.assert_owner();
+        self.known_answers.remove(&word.to_lowercase());
+    }
+
+    #[payable]
+    pub fn request(
+        &mut self,
+        message: String,
+        agent: Option<String>,
+        provenance: Option<RequestSignature>,
+        locale: Option<String>,
+        tags: Option<Vec<String>>,
+    ) {
+        require!(
+            env::prepaid_gas() >= self.min_prepaid_gas,
+            "ERR_INSUFFICIENT_PREPAID_GAS"
+        );
         self.assert_paused();
+        self.assert_maintenance();
+        let deposit = env::attached_deposit();
+        require!(deposit >= self.min_deposit, "ERR_DEPOSIT_TOO_LOW");
+        require!(!self.is_champion_immune(), "ERR_CHAMPION_IMMUNE");
 
+        // `min_prepaid_gas` covers the whole floor, the body has spent part
+        // of its own share by now but the yield must still be funded.
         require!(
-            remaining_gas() >= MIN_REQUEST_GAS,
+            remaining_gas() >= self.callback_gas(),
             "Not enough remaining gas to make the request"
         );
 
+        self.timeout_expired_requests(MAX_TIMEOUTS_PER_REQUEST);
+
         let message = message.to_lowercase();
         assert!(is_valid_string(message.as_str()), "Illegal input string");
+        if let Some(locale) = &locale {
+            require!(is_valid_locale(locale), "ERR_INVALID_LOCALE");
+        }
+        let tags: Vec<String> = tags
+            .unwrap_or_default()
+            .into_iter()
+            .map(|tag| tag.to_lowercase())
+            .collect();
+        require!(tags.len() <= MAX_TAGS, "ERR_TOO_MANY_TAGS");
+        for tag in &tags {
+            require!(
+                !tag.is_empty() && tag.len() <= MAX_TAG_LEN && is_valid_string(tag),
+                "ERR_INVALID_TAG"
+            );
+        }
+        require!(
+            message.len() as u32 >= self.min_champion_len,
+            "ERR_GUESS_TOO_SHORT"
+        );
+        require!(
+            !self.feature_flags.reject_substring_guesses
+                || !(self.current_champion.contains(&message)
+                    || message.contains(&self.current_champion)),
+            "ERR_SUBSTRING_GUESS"
+        );
+        require!(!self.is_recently_crowned(&message), "ERR_RECENTLY_CROWNED");
+
+        if let Some(provenance) = &provenance {
+            require!(
+                provenance.public_key.len() <= MAX_PROVENANCE_FIELD_LEN
+                    && provenance.signature.len() <= MAX_PROVENANCE_FIELD_LEN,
+                "ERR_PROVENANCE_TOO_LONG"
+            );
+            require!(
+                verify_signature(
+                    &provenance.public_key,
+                    KeyType::Ed25519,
+                    &near_sdk::borsh::to_vec(&(&message, provenance.nonce)).unwrap(),
+                    &provenance.signature,
+                ),
+                "ERR_INVALID_PROVENANCE"
+            );
+        }
 
         let account_id: AccountId = env::predecessor_account_id();
+        require!(
+            !self.banned_accounts.contains(&account_id),
+            "ERR_ACCOUNT_BANNED"
+        );
+        require!(
+            self.feature_flags.operator_can_play || account_id != self.operator_id,
+            "ERR_OPERATOR_CANNOT_PLAY"
+        );
+        require!(
+            !self.feature_flags.require_direct_call || account_id == env::signer_account_id(),
+            "ERR_INDIRECT_CALL"
+        );
+
+        if let Some(provenance) = &provenance {
+            let nonce = self.get_nonce(account_id.clone());
+            require!(provenance.nonce == nonce, "ERR_BAD_NONCE");
+            self.nonces.insert(&account_id, &(nonce + 1));
+        }
+
+        require!(
+            !self.feature_flags.reject_duplicate_in_flight
+                || !self.in_flight_guesses.contains_key(&message),
+            "ERR_DUPLICATE_IN_FLIGHT"
+        );
+
+        self.check_repeat_guess(&account_id, &message);
+        self.record_challenger(&account_id);
+        self.record_account_deposit(&account_id, deposit);
+        self.total_collected = self.total_collected.saturating_add(deposit);
+
+        let agent = self.agent_config(&agent.unwrap_or_else(|| self.agent_name.clone()));
+
+        let new_request = NewRequest {
+            originator_id: account_id,
+            message,
+            agent: agent.name,
+            provenance,
+            locale,
+            tags,
+            deposit,
+            bracket_match: None,
+        };
+
+        if let Some(wins) = self.known_answers.get(&new_request.message) {
+            self.resolve_known_answer(new_request, wins);
+            return;
+        }
+
+        if self.is_overloaded() {
+            require!(
+                self.feature_flags.queue_when_overloaded,
+                "ERR_TOO_MANY_OPEN_REQUESTS"
+            );
+            self.enqueue_request(new_request);
+            return;
+        }
+
+        let yield_promise = self.create_request(new_request);
+
+        let request_id = self.num_requests - 1;
+        let gas_used = env::used_gas();
+        self.update_timeline(request_id, |timeline| {
+            timeline.request_gas_used = Some(gas_used)
+        });
+        log!(
+            "Request {} used {} of the {} minimum",
+            request_id,
+            gas_used,
+            self.min_prepaid_gas
+        );
+
+        env::promise_return(yield_promise);
+    }
+
+    pub fn respond(&mut self, data_id: CryptoHash, request_id: RequestId, response: Response) {
+        require!(request_id < self.num_requests, "ERR_BAD_REQUEST_ID");
+        self.assert_operator();
+
+        if let Err(error) = self.validate_response(request_id, &response) {
+            env::panic_str(error);
+        }
+        // Only the stored id is ever resumed, `data_id` guards against
+        // responses meant for a request since fixed by `repair_request`.
+        let request = self.get_request(request_id);
+        require!(data_id == request.data_id, "ERR_DATA_ID_MISMATCH");
+
+        self.responses.insert(&request_id, &response);
+        self.record_pending_win(request_id, &request, &response);
+        // Recorded here rather than in `await_response`, so the metrics stay
+        // in step with the timeline whatever the resume leads to.
+        self.update_timeline(request_id, |timeline| {
+            timeline.responded = Some(env::block_height())
+        });
+        self.record_latency(&request);
+
+        // A yield past the protocol timeout has already resumed on its own,
+        // storing the response then would leave the request stuck.
+        require!(
+            env::promise_yield_resume(
+                &request.data_id,
+                serde_json::to_vec(&(request_id,)).unwrap(),
+            ),
+            "ERR_YIELD_EXPIRED"
+        );
+    }
+
+    /// Dry run of the checks `respond` applies, so operators can pre-flight
+    /// a response instead of submitting a failing transaction.
+    pub fn verify_response(&self, request_id: RequestId, response: Response) -> bool {
+        self.validate_response(request_id, &response).is_ok()
+    }
+
+    /// Crowns `word` for `owner` outside the request flow, on a ruling the
+    /// primary agent reached on its own. The agent signs the borsh encoding
+    /// of `(word, owner, reason, current_champion, nonce)` with the `nonce`
+    /// from `get_crown_nonce`, so a signature is only valid against the
+    /// reign it ends and cannot be replayed once that champion returns.
+    pub fn crown_champion_verified(
+        &mut self,
+        word: String,
+        owner: AccountId,
+        signature: String,
+        reason: String,
+    ) {
+        self.assert_operator();
+
+        let word = word.to_lowercase();
+        assert!(is_valid_string(word.as_str()), "Illegal input string");
+        require!(
+            word.len() as u32 >= self.min_champion_len,
+            "ERR_CHAMPION_TOO_SHORT"
+        );
+        require!(
+            verify_signature(
+                &self.agent_public_key,
+                self.agent_key_type,
+                &crown_signing_payload(
+                    &word,
+                    &owner,
+                    &reason,
+                    &self.current_champion,
+                    self.get_crown_nonce(),
+                ),
+                &signature,
+            ),
+            "ERR_INVALID_SIGNATURE"
+        );
+
+        if self.set_champion(word, owner.clone(), &reason) {
+            log!("Player {} crowned by the agent: {}", owner, reason);
+        }
+    }
+
+    /// Nonce `crown_champion_verified` signatures cover: the number of
+    /// champion history records, which every crowning, decay and new season
+    /// increments.
+    pub fn get_crown_nonce(&self) -> u64 {
+        self.champion_history.len()
+    }
+
+    #[private]
+    pub fn await_response(&mut self, request_id: RequestId) -> PromiseOrValue<Response> {
+        let response: Option<Response> = self.responses.get(&request_id);
+        if let Some(response) = response {
+            let request = self.remove_open_request(request_id).expect("Wrong request");
+
+            // Agent responses are always signed, unsigned ones come from `close_request`.
+            if response.signature.is_none() {
+                log_closed_request(&request, request_id, &response);
+                self.settle_escrow(request_id, &request, &Outcome::Unresolved);
+                self.log_resolution(
+                    request_id,
+                    &request,
+                    Outcome::Unresolved,
+                    response.data.clone(),
+                    None,
+                );
+                return PromiseOrValue::Value(response);
+            }
+
+            // A malformed ruling resolves the request as a loss instead of
+            // panicking, and the reason stays queryable via `get_request_error`.
+            match self.apply_ruling(request_id, &request, &response) {
+                Ok((outcome, reason)) => {
+                    self.record_agent_health(response.ok);
+                    if outcome == Outcome::Won {
+                        self.total_wins += 1;
+                    } else {
+                        self.total_losses += 1;
+                    }
+                    self.settle_escrow(request_id, &request, &outcome);
+                    self.log_resolution(
+                        request_id,
+                        &request,
+                        outcome,
+                        Some(reason),
+                        response.signature.clone(),
+                    );
+                }
+                Err(error) => {
+                    self.record_agent_health(false);
+                    log!("Request {} failed: {}", request_id, error);
+                    self.request_errors.insert(&request_id, &error);
+                    self.settle_escrow(request_id, &request, &Outcome::Unresolved);
+                    self.log_resolution(
+                        request_id,
+                        &request,
+                        Outcome::Unresolved,
+                        Some(error),
+                        response.signature.clone(),
+                    );
+                }
+            }
+
+            PromiseOrValue::Value(response)
+        } else {
+            panic!("Response is missing for {}", request_id);
+        }
+    }
+
+    pub fn remove_request(&mut self, request_id: RequestId) {
+        require!(request_id < self.num_requests, "ERR_BAD_REQUEST_ID");
+        self.assert_operator();
+        if let Some(request) = self.remove_open_request(request_id) {
+            self.settle_escrow(request_id, &request, &Outcome::Unresolved);
+        }
+    }
+
+    /// Recovery for a request whose stored yield `data_id` is wrong, so
+    /// `respond` can never resume it. Replaces it with `data_id`, base58 of
+    /// the 32 bytes, or cancels the request when `data_id` is omitted.
+    pub fn repair_request(&mut self, request_id: RequestId, data_id: Option<String>) {
+        require!(request_id < self.num_requests, "ERR_BAD_REQUEST_ID");
+        self.assert_owner();
+        let mut request = self
+            .requests
+            .get(&request_id)
+            .expect("Request ID not found");
+        require!(
+            !self.responses.contains_key(&request_id),
+            "ERR_ALREADY_RESPONDED"
+        );
+
+        match data_id {
+            Some(data_id) => {
+                request.data_id = near_sdk::bs58::decode(data_id)
+                    .into_vec()
+                    .ok()
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .expect("ERR_BAD_DATA_ID");
+                self.requests.insert(&request_id, &request);
+            }
+            None => self.close_request(request_id, &request, cancel_response()),
+        }
+    }
+
+    /// Re-emits `run_agent` for up to `max` open requests that are not already
+    /// being resolved, optionally moving them to another registered `agent`,
+    /// so a different worker can pick them up. Returns the number re-emitted.
+    pub fn reassign_requests(&mut self, max: u64, agent: Option<String>) -> u64 {
+        self.assert_owner();
+
+        let agent = agent.map(|agent| self.agent_config(&agent));
+        let requests: Vec<(RequestId, Request)> = self
+            .requests
+            .iter()
+            .filter(|(request_id, _)| !self.responses.contains_key(request_id))
+            .take(max as usize)
+            .collect();
+
+        for (request_id, mut request) in requests.iter().cloned() {
+            if let Some(agent) = &agent {
+                self.remove_agent_request(&request.agent);
+                self.add_agent_request(&agent.name);
+                request.agent = agent.name.clone();
+                self.requests.insert(&request_id, &request);
+            }
+            self.update_timeline(request_id, |timeline| {
+                timeline.agent_notified = env::block_height()
+            });
+
+            events::emit::run_agent(
+                self.event_forwarder.as_ref(),
+                &request.agent,
+                &request.message,
+                Some(request_id),
+                &request.originator_id,
+                self.deadline_block(&request),
+            );
+        }
+
+        requests.len() as u64
+    }
+
+    /// Cancels up to `max` of the caller's open requests that are not already
+    /// being resolved. Returns the number of requests cancelled.
+    pub fn cancel_my_requests(&mut self, max: u64) -> u64 {
+        self.assert_maintenance();
+
+        let account_id = env::predecessor_account_id();
+        let request_ids: Vec<RequestId> = match self.account_requests.get(&account_id) {
+            Some(request_ids) => request_ids
+                .iter()
+                .filter(|request_id| !self.responses.contains_key(request_id))
+                .take(max as usize)
+                .collect(),
+            None => vec![],
+        };
+
+        for request_id in &request_ids {
+            let request = self.get_request(*request_id);
+            self.close_request(*request_id, &request, cancel_response());
+        }
+
+        request_ids.len() as u64
+    }
+}
+
+impl Contract {
+    /// Resolves a guess found in `known_answers` on the spot, without a yield
+    /// or the agent. It still takes a request id and goes through the same
+    /// bookkeeping as an agent ruling, so it shows up in the responses log,
+    /// histories and stats.
+    fn resolve_known_answer(&mut self, new_request: NewRequest, wins: bool) {
+        let request_id = self.num_requests;
+        self.num_requests += 1;
+        let request = Request {
+            data_id: [0; 32],
+            originator_id: new_request.originator_id,
+            message: new_request.message,
+            agent: new_request.agent,
+            provenance: new_request.provenance,
+            created_block: env::block_height(),
+            champion_at_request: self.current_champion.clone(),
+            challenge: request_challenge(request_id),
+            locale: new_request.locale,
+            acked: false,
+            tags: new_request.tags,
+            deadline_extension: 0,
+            bracket_match: None,
+        };
+        self.start_timeline(request_id);
+
+        self.record_ruling(&request, wins, wins);
+        let outcome = if wins {
+            self.total_wins += 1;
+            self.crown_winner(&request, request.message.clone(), KNOWN_ANSWER_REASON);
+            Outcome::Won
+        } else {
+            self.total_losses += 1;
+            log!(
+                "Player {} lost: {}",
+                request.originator_id,
+                KNOWN_ANSWER_REASON
+            );
+            Outcome::Lost
+        };
+        self.distribute_deposit(&request.originator_id, new_request.deposit, &outcome);
+        self.log_resolution(
+            request_id,
+            &request,
+            outcome,
+            Some(KNOWN_ANSWER_REASON.to_string()),
+            None,
+        );
+    }
+
+    /// Player and champion stats and the matchup every ruling on a challenge
+    /// of the champion records, whether from the agent or a known answer.
+    fn record_ruling(&mut self, request: &Request, ruled_win: bool, guess_wins: bool) {
+        self.record_result(&request.originator_id, guess_wins);
+        self.record_champion_result(&request.champion_at_request, ruled_win);
+        self.matchups.insert(
+            &(request.message.clone(), request.champion_at_request.clone()),
+            &ruled_win,
+        );
+    }
+
+    /// Crowns the winner of `request` and pays the win reward, or only adds
+    /// `new_champion` to the pool during the warm-up.
+    fn crown_winner(&mut self, request: &Request, new_champion: String, reason: &str) {
+        if self.is_warming_up() {
+            self.all_champions.insert(&new_champion);
+            log!(
+                "Player {} won during warm-up, {} joins the pool: {}",
+                request.originator_id,
+                new_champion,
+                reason
+            );
+            return;
+        }
+
+        let reward = self.get_potential_reward();
+        if self.set_champion(new_champion, request.originator_id.clone(), reason) {
+            self.pay_reward(&request.originator_id, reward);
+        }
+        log!("Player {} won: {}", request.originator_id, reason);
+    }
+
+    /// Gas a request needs: its own body plus what the yield reserves.
+    pub(crate) fn request_gas_floor(&self) -> Gas {
+        MIN_REQUEST_GAS.saturating_add(self.callback_gas())
+    }
+
+    /// Gas every yield reserves for `await_response`, including what a
+    /// trophy mint attaches from within it.
+    pub(crate) fn callback_gas(&self) -> Gas {
+        self.yield_callback_gas
+            .saturating_add(self.trophy_mint_gas())
+    }
+
+    pub(crate) fn set_min_prepaid_gas_checked(&mut self, min_prepaid_gas: Gas) {
+        require!(
+            min_prepaid_gas >= self.request_gas_floor(),
+            "ERR_PREPAID_GAS_TOO_LOW"
+        );
+        self.min_prepaid_gas = min_prepaid_gas;
+    }
+
+    /// Raises `min_prepaid_gas` along with it, so requests that could not
+    /// fund the yield are still rejected upfront.
+    pub(crate) fn set_yield_callback_gas_checked(&mut self, yield_callback_gas: Gas) {
+        require!(
+            yield_callback_gas >= MIN_RESPONSE_GAS && yield_callback_gas <= MAX_RESPONSE_GAS,
+            "ERR_BAD_YIELD_CALLBACK_GAS"
+        );
+        self.yield_callback_gas = yield_callback_gas;
+        self.min_prepaid_gas = std::cmp::max(self.min_prepaid_gas, self.request_gas_floor());
+    }
+
+    fn validate_response(&self, request_id: RequestId, response: &Response) -> Result<(), &str> {
+        if request_id >= self.num_requests {
+            return Err("ERR_BAD_REQUEST_ID");
+        }
+        let Some(request) = self.requests.get(&request_id) else {
+            return Err("Request ID not found");
+        };
+        if self.feature_flags.require_ack && !request.acked {
+            return Err("ERR_REQUEST_NOT_ACKED");
+        }
+        // An echoed challenge must match even while the flag is off.
+        if (self.feature_flags.require_challenge || response.challenge.is_some())
+            && response.challenge.as_ref() != Some(&request.challenge)
+        {
+            return Err("ERR_CHALLENGE_MISMATCH");
+        }
+        if self.feature_flags.require_matching_originator
+            && response.originator.as_ref() != Some(&request.originator_id)
+        {
+            return Err("ERR_ORIGINATOR_MISMATCH");
+        }
+        if !self.is_signed_by_agent(request_id, response) {
+            return Err("ERR_INVALID_SIGNATURE");
+        }
+        Ok(())
+    }
+
+    /// Creates the yield awaiting the agent and stores the open request.
+    pub(crate) fn create_request(&mut self, new_request: NewRequest) -> PromiseIndex {
+        let NewRequest {
+            originator_id: account_id,
+            message,
+            agent,
+            provenance,
+            locale,
+            tags,
+            deposit,
+            bracket_match,
+        } = new_request;
         let request_id: RequestId = self.num_requests;
+        let storage_before = env::storage_usage();
 
         let yield_promise = env::promise_yield_create(
             "await_response",
-            &serde_json::to_vec(&(request_id,)).unwrap(),
-            MIN_RESPONSE_GAS,
+            serde_json::to_vec(&(request_id,)).unwrap(),
+            self.callback_gas(),
             GasWeight(0),
             DATA_ID_REGISTER,
         );
@@ -191,81 +1413,282 @@ impl Contract {
             data_id,
             originator_id: account_id.clone(),
             message: message.clone(),
+            agent,
+            provenance,
+            created_block: env::block_height(),
+            champion_at_request: bracket_match
+                .as_ref()
+                .map_or_else(|| self.current_champion.clone(), |m| m.defender.clone()),
+            challenge: request_challenge(request_id),
+            locale,
+            acked: false,
+            tags,
+            deadline_extension: 0,
+            bracket_match,
         };
 
         self.requests.insert(&request_id, &request_with_data_id);
+        self.add_agent_request(&request_with_data_id.agent);
+
+        let mut request_ids = self.account_requests.get(&account_id).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::AccountRequestsInner {
+                account_hash: env::sha256_array(account_id.as_bytes()),
+            })
+        });
+        request_ids.insert(&request_id);
+        self.account_requests.insert(&account_id, &request_ids);
+        let in_flight = self.in_flight_guesses.get(&message).unwrap_or(0);
+        self.in_flight_guesses.insert(&message, &(in_flight + 1));
         self.num_requests += 1;
+        self.start_timeline(request_id);
+        self.escrows.insert(&request_id, &deposit);
+        self.stake_storage(request_id, deposit, storage_before);
 
-        events::emit::run_agent(&self.agent_name, &message, Some(request_id));
+        events::emit::run_agent(
+            self.event_forwarder.as_ref(),
+            &request_with_data_id.agent,
+            &message,
+            Some(request_id),
+            &account_id,
+            self.deadline_block(&request_with_data_id),
+        );
+        events::emit::request_created(
+            self.event_forwarder.as_ref(),
+            request_id,
+            &request_with_data_id,
+        );
 
-        env::promise_return(yield_promise);
+        yield_promise
     }
 
-    pub fn respond(&mut self, data_id: CryptoHash, request_id: RequestId, response: Response) {
-        self.assert_operator();
+    /// Single cleanup point for an open request and its pending response.
+    pub(crate) fn remove_open_request(&mut self, request_id: RequestId) -> Option<Request> {
+        let storage_before = env::storage_usage();
+        self.responses.remove(&request_id);
+        self.claims.remove(&request_id);
+        let request = self.requests.remove(&request_id)?;
 
-        if self.requests.get(&request_id).is_none() {
-            panic!("Request ID not found");
+        if let Some(mut request_ids) = self.account_requests.get(&request.originator_id) {
+            request_ids.remove(&request_id);
+            if request_ids.is_empty() {
+                self.account_requests.remove(&request.originator_id);
+            } else {
+                self.account_requests
+                    .insert(&request.originator_id, &request_ids);
+            }
+        }
+        match self.in_flight_guesses.get(&request.message) {
+            Some(in_flight) if in_flight > 1 => {
+                self.in_flight_guesses
+                    .insert(&request.message, &(in_flight - 1));
+            }
+            _ => {
+                self.in_flight_guesses.remove(&request.message);
+            }
         }
+        self.remove_agent_request(&request.agent);
+        self.remove_pending_win(request_id, &request);
+        if request.bracket_match.is_some() {
+            self.release_bracket_match();
+        }
+        self.refund_storage(request_id, &request.originator_id, storage_before);
+
+        Some(request)
+    }
 
+    /// Resolves the yield with an unsigned `response` instead of an agent
+    /// ruling. If the yield is already gone nothing will call
+    /// `await_response`, so the request is dropped here.
+    pub(crate) fn close_request(
+        &mut self,
+        request_id: RequestId,
+        request: &Request,
+        response: Response,
+    ) {
         self.responses.insert(&request_id, &response);
 
-        env::promise_yield_resume(&data_id, &serde_json::to_vec(&(request_id,)).unwrap());
+        if !env::promise_yield_resume(
+            &request.data_id,
+            serde_json::to_vec(&(request_id,)).unwrap(),
+        ) {
+            self.remove_open_request(request_id);
+            log_closed_request(request, request_id, &response);
+            self.settle_escrow(request_id, request, &Outcome::Unresolved);
+            self.log_resolution(
+                request_id,
+                request,
+                Outcome::Unresolved,
+                response.data,
+                None,
+            );
+        }
     }
 
-    #[private]
-    pub fn await_response(&mut self, request_id: RequestId) -> PromiseOrValue<Response> {
-        let response: Option<Response> = self.responses.get(&request_id);
-        if let Some(response) = response {
-            self.responses.remove(&request_id);
+    /// Validates the agent ruling before touching any state, then records it.
+    fn apply_ruling(
+        &mut self,
+        request_id: RequestId,
+        request: &Request,
+        response: &Response,
+    ) -> Result<(Outcome, String), String> {
+        let response_text = response.data.clone().unwrap_or_default();
 
-            let request = self.requests.remove(&request_id).expect("Wrong request");
+        let parsed_message = serde_json::from_str::<ResponseMsg>(&response_text)
+            .map_err(|_| "Wrong response message format".to_string())?;
 
-            let response_text = response.data.clone().unwrap_or_default();
+        if parsed_message.data_is_reference && !is_valid_content_reference(&parsed_message.reason) {
+            return Err("Illegal response data reference".to_string());
+        }
+        if parsed_message.current_champion != request.champion_at_request {
+            return Err("Illegal current champion".to_string());
+        }
+        if parsed_message.bonus_points > self.max_bonus_points {
+            return Err("Bonus points over the cap".to_string());
+        }
 
-            let parsed_message = serde_json::from_str::<ResponseMsg>(&response_text)
-                .expect("Wrong response message format");
+        let ruled_win = response.ok && parsed_message.guess_wins;
 
-            assert_eq!(
-                parsed_message.current_champion, self.current_champion,
-                "Illegal current champion"
+        // Bracket matches only advance the bracket, the champion is untouched.
+        if let Some(bracket_match) = &request.bracket_match {
+            self.matchups.insert(
+                &(request.message.clone(), request.champion_at_request.clone()),
+                &ruled_win,
             );
-
-            if response.ok && parsed_message.guess_wins {
-                self.set_champion(
-                    request.message.to_lowercase(),
-                    request.originator_id.clone(),
-                );
-                log!(
-                    "Player {} won: {}",
-                    request.originator_id.clone(),
-                    parsed_message.reason
-                );
+            let winner = if ruled_win {
+                request.message.clone()
             } else {
-                log!(
-                    "Player {} lost: {}",
-                    request.originator_id.clone(),
-                    parsed_message.reason
-                );
+                request.champion_at_request.clone()
+            };
+            log!(
+                "Bracket round {} match {} won by {}: {}",
+                bracket_match.round,
+                bracket_match.index,
+                winner,
+                parsed_message.reason
+            );
+            self.advance_bracket(bracket_match, winner);
+            let outcome = if ruled_win {
+                Outcome::Won
+            } else {
+                Outcome::Lost
+            };
+            return Ok((outcome, parsed_message.reason));
+        }
+
+        // Of several wins against the same champion only one takes the title,
+        // see `TieBreak`. Later ones find it already dethroned.
+        let dethroned_meanwhile = request.champion_at_request != self.current_champion;
+        let outranked = ruled_win && !dethroned_meanwhile && self.is_outranked(request_id, request);
+        let guess_wins = ruled_win && !dethroned_meanwhile && !outranked;
+        let outcome = if guess_wins {
+            Outcome::Won
+        } else if ruled_win {
+            Outcome::Superseded
+        } else {
+            Outcome::Lost
+        };
+
+        let new_champion = match &parsed_message.canonical_champion {
+            Some(canonical_champion) if ruled_win => {
+                let canonical_champion = canonical_champion.to_lowercase();
+                if !is_valid_string(canonical_champion.as_str())
+                    || (canonical_champion.len() as u32) < self.min_champion_len
+                {
+                    return Err("Illegal canonical champion".to_string());
+                }
+                canonical_champion
             }
+            _ => request.message.to_lowercase(),
+        };
 
-            PromiseOrValue::Value(response)
+        self.record_ruling(request, ruled_win, guess_wins);
+        self.award_bonus_points(&request.originator_id, parsed_message.bonus_points);
+
+        if ruled_win && dethroned_meanwhile {
+            log!(
+                "Player {} won but {} was dethroned before the win landed: {}",
+                request.originator_id.clone(),
+                request.champion_at_request,
+                parsed_message.reason
+            );
+        } else if outranked {
+            log!(
+                "Player {} won but lost the tie-break against {}: {}",
+                request.originator_id.clone(),
+                request.champion_at_request,
+                parsed_message.reason
+            );
+        } else if guess_wins {
+            self.crown_winner(request, new_champion, &parsed_message.reason);
         } else {
-            panic!("Response is missing for {}", request_id);
+            log!(
+                "Player {} lost: {}",
+                request.originator_id.clone(),
+                parsed_message.reason
+            );
         }
+
+        Ok((outcome, parsed_message.reason))
     }
 
-    pub fn remove_request(&mut self, request_id: RequestId) {
-        self.assert_operator();
-        self.requests.remove(&request_id);
-        self.responses.remove(&request_id);
+    /// Crowns `new_champion` as the successor of the current reign.
+    fn set_champion(
+        &mut self,
+        new_champion: String,
+        new_champion_owner: AccountId,
+        reason: &str,
+    ) -> bool {
+        let predecessor = self.champion_history.len().checked_sub(1);
+        self.replace_champion(new_champion, new_champion_owner, reason, predecessor)
     }
-}
 
-impl Contract {
-    fn set_champion(&mut self, new_champion: String, new_champion_owner: AccountId) {
+    /// `predecessor` is the history index of the reign a decay of the new
+    /// champion restores. Returns `false` without touching any state when the
+    /// same word and owner already hold the title, so per-reign bookkeeping
+    /// is never double counted.
+    fn replace_champion(
+        &mut self,
+        new_champion: String,
+        new_champion_owner: AccountId,
+        reason: &str,
+        predecessor: Option<u64>,
+    ) -> bool {
+        if new_champion == self.current_champion && new_champion_owner == self.champion_owner {
+            return false;
+        }
+
+        self.end_reign();
+
         self.all_champions.insert(&new_champion);
-        self.current_champion = new_champion;
+        let dethroned = std::mem::replace(&mut self.current_champion, new_champion);
         self.champion_owner = new_champion_owner;
+        self.champion_since_block = env::block_height();
+        self.champion_reason = Some(reason.to_string());
+        self.immune_until_block = 0;
+        self.record_champion(predecessor);
+        self.start_crowned_cooldown();
+        if reason != DECAY_REASON {
+            self.record_conquest(dethroned);
+            self.mint_trophy(reason);
+        }
+
+        if self.target_champion.as_ref() == Some(&self.current_champion) {
+            self.paused = true;
+            events::emit::game_won(
+                self.event_forwarder.as_ref(),
+                &self.current_champion,
+                &self.champion_owner,
+            );
+        }
+        true
     }
 }
+
+fn log_closed_request(request: &Request, request_id: RequestId, response: &Response) {
+    log!(
+        "Player {} request {} {}",
+        request.originator_id,
+        request_id,
+        response.data.as_deref().unwrap_or("timed out")
+    );
+}
@@ -0,0 +1,79 @@
+use crate::*;
+
+/// Champion sets above this size are refused to keep the view within gas.
+const MAX_MERKLE_CHAMPIONS: u64 = 2_000;
+
+/// One level of an inclusion proof, from the leaf up to the root.
+#[derive(Serialize, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MerkleProofStep {
+    pub sibling: String,
+    pub sibling_is_left: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Merkle root over the champion set sorted bytewise. Leaves are
+    /// `sha256(word)`, parents `sha256(left || right)`, and an odd node at the
+    /// end of a level moves up unchanged. Hashes are base58 encoded.
+    pub fn get_champions_root(&self) -> String {
+        let levels = merkle_levels(&self.sorted_champions());
+        near_sdk::bs58::encode(levels.last().unwrap()[0]).into_string()
+    }
+
+    /// Sibling hashes proving `word` is part of `get_champions_root`.
+    pub fn get_champion_proof(&self, word: String) -> Option<Vec<MerkleProofStep>> {
+        let champions = self.sorted_champions();
+        let mut index = champions.binary_search(&word.to_lowercase()).ok()?;
+
+        let levels = merkle_levels(&champions);
+        let mut proof = vec![];
+        for level in &levels[..levels.len() - 1] {
+            let sibling = index ^ 1;
+            if sibling < level.len() {
+                proof.push(MerkleProofStep {
+                    sibling: near_sdk::bs58::encode(level[sibling]).into_string(),
+                    sibling_is_left: sibling < index,
+                });
+            }
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+}
+
+impl Contract {
+    pub(crate) fn sorted_champions(&self) -> Vec<String> {
+        require!(
+            self.all_champions.len() <= MAX_MERKLE_CHAMPIONS,
+            "ERR_TOO_MANY_CHAMPIONS"
+        );
+        let mut champions = self.all_champions.to_vec();
+        champions.sort();
+        champions
+    }
+}
+
+/// All tree levels, leaves first and the single root last.
+fn merkle_levels(champions: &[String]) -> Vec<Vec<CryptoHash>> {
+    let leaves: Vec<CryptoHash> = champions
+        .iter()
+        .map(|champion| env::sha256_array(champion.as_bytes()))
+        .collect();
+    let mut levels = vec![leaves];
+    while levels.last().unwrap().len() > 1 {
+        let parents = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => env::sha256_array([left.as_slice(), right.as_slice()].concat()),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(parents);
+    }
+    levels
+}
@@ -0,0 +1,161 @@
+use crate::*;
+
+/// Layout version of the contract state, bumped with every migration.
+pub(crate) const STATE_VERSION: u32 = 1;
+/// Raw storage key of the version, outside the `StorageKey` prefixes. Absent
+/// for the original layout, which predates versioning.
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+
+/// `Request` as stored by the original layout.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "near_sdk::borsh")]
+struct RequestV0 {
+    data_id: CryptoHash,
+    originator_id: AccountId,
+    message: String,
+}
+
+/// `Response` as stored by the original layout.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "near_sdk::borsh")]
+struct ResponseV0 {
+    ok: bool,
+    data: Option<String>,
+    signature: Option<String>,
+}
+
+/// The original contract state, before any versioning.
+#[derive(BorshDeserialize)]
+#[borsh(crate = "near_sdk::borsh")]
+struct ContractV0 {
+    agent_name: String,
+    agent_public_key: String,
+    agent_system_prompt: String,
+    paused: bool,
+    requests: UnorderedMap<RequestId, RequestV0>,
+    responses: LookupMap<RequestId, ResponseV0>,
+    num_requests: u64,
+    owner_id: AccountId,
+    operator_id: AccountId,
+    current_champion: String,
+    champion_owner: AccountId,
+    all_champions: UnorderedSet<String>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Upgrades the state left by the previous code to `STATE_VERSION`.
+    /// Settings missing from the old layout take the defaults of `new`, and
+    /// the current champion starts a fresh history. Open requests keep their
+    /// id and yield and get a new challenge, no tags and the primary agent;
+    /// their deadline counts from the migration. Submitted responses still
+    /// to be resolved are kept.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        match state_version() {
+            0 => Self::migrate_from_v0(),
+            _ => env::panic_str("ERR_ALREADY_MIGRATED"),
+        }
+    }
+
+    pub fn get_state_version(&self) -> u32 {
+        state_version()
+    }
+}
+
+impl Contract {
+    fn migrate_from_v0() -> Self {
+        let mut old: ContractV0 = env::state_read().expect("ERR_NO_STATE");
+        let old_requests = old.requests.to_vec();
+        old.requests.clear();
+
+        let mut contract = Self::new(
+            old.owner_id,
+            old.operator_id,
+            old.current_champion,
+            Some(old.champion_owner),
+            None,
+            old.agent_name,
+            old.agent_system_prompt,
+            old.agent_public_key,
+            None,
+        );
+        contract.paused = old.paused;
+        contract.num_requests = old.num_requests;
+        contract.all_champions = old.all_champions;
+        contract.oldest_open_request = old_requests
+            .iter()
+            .map(|(request_id, _)| *request_id)
+            .min()
+            .unwrap_or(old.num_requests);
+
+        for (request_id, old_request) in old_requests {
+            let request = Request {
+                data_id: old_request.data_id,
+                originator_id: old_request.originator_id,
+                message: old_request.message,
+                agent: contract.agent_name.clone(),
+                provenance: None,
+                created_block: env::block_height(),
+                champion_at_request: contract.current_champion.clone(),
+                challenge: request_challenge(request_id),
+                locale: None,
+                acked: false,
+                tags: vec![],
+                deadline_extension: 0,
+                bracket_match: None,
+            };
+            contract.index_migrated_request(request_id, &request);
+
+            if let Some(response) = old.responses.remove(&request_id) {
+                contract.responses.insert(
+                    &request_id,
+                    &Response {
+                        ok: response.ok,
+                        data: response.data,
+                        signature: response.signature,
+                        challenge: None,
+                        originator: None,
+                    },
+                );
+            }
+        }
+
+        log!("Migrated to state version {}", STATE_VERSION);
+        contract
+    }
+
+    /// Stores `request` with the per-account, per-guess and per-agent
+    /// bookkeeping `create_request` keeps for an open request.
+    fn index_migrated_request(&mut self, request_id: RequestId, request: &Request) {
+        self.requests.insert(&request_id, request);
+        self.add_agent_request(&request.agent);
+
+        let mut request_ids = self
+            .account_requests
+            .get(&request.originator_id)
+            .unwrap_or_else(|| {
+                UnorderedSet::new(StorageKey::AccountRequestsInner {
+                    account_hash: env::sha256_array(request.originator_id.as_bytes()),
+                })
+            });
+        request_ids.insert(&request_id);
+        self.account_requests
+            .insert(&request.originator_id, &request_ids);
+        let in_flight = self.in_flight_guesses.get(&request.message).unwrap_or(0);
+        self.in_flight_guesses
+            .insert(&request.message, &(in_flight + 1));
+        self.start_timeline(request_id);
+    }
+}
+
+pub(crate) fn state_version() -> u32 {
+    env::storage_read(STATE_VERSION_KEY).map_or(0, |version| {
+        u32::from_le_bytes(version.try_into().expect("ERR_CORRUPT_STATE_VERSION"))
+    })
+}
+
+pub(crate) fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &STATE_VERSION.to_le_bytes());
+}
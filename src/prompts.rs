@@ -0,0 +1,84 @@
+use crate::*;
+
+/// Named parts of the primary agent prompt, assembled in declaration order.
+#[derive(
+    BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone, Copy, PartialEq,
+)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum PromptSegment {
+    Instructions,
+    Persona,
+    Constraints,
+}
+
+pub(crate) const PROMPT_SEGMENTS: [PromptSegment; 3] = [
+    PromptSegment::Instructions,
+    PromptSegment::Persona,
+    PromptSegment::Constraints,
+];
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_prompt_segment(&self, segment: PromptSegment) -> Option<String> {
+        self.prompt_segments.get(&segment)
+    }
+
+    /// Sets one segment of the primary agent prompt, or removes it with
+    /// `None`. While any segment is set, the assembled segments replace
+    /// `agent_system_prompt`. `{champion}` and `{question}` are filled in
+    /// for the challenged champion.
+    pub fn set_prompt_segment(&mut self, segment: PromptSegment, text: Option<String>) {
+        self.assert_owner();
+        match text {
+            Some(text) => self.prompt_segments.insert(&segment, &text),
+            None => self.prompt_segments.remove(&segment),
+        };
+    }
+
+    /// The assembled prompt for the current champion, `None` when no segment is set.
+    pub fn get_assembled_prompt(&self) -> Option<String> {
+        self.assemble_prompt(&self.current_champion)
+    }
+}
+
+impl Contract {
+    /// Set segments in `PROMPT_SEGMENTS` order, separated by blank lines.
+    pub(crate) fn assemble_prompt(&self, champion: &str) -> Option<String> {
+        let segments: Vec<String> = PROMPT_SEGMENTS
+            .iter()
+            .filter_map(|segment| self.prompt_segments.get(segment))
+            .collect();
+        if segments.is_empty() {
+            return None;
+        }
+
+        Some(
+            segments
+                .join("\n\n")
+                .replace("{question}", &self.question_for(champion))
+                .replace("{champion}", champion),
+        )
+    }
+
+    /// The template must contain `{champion}`, `None` restores the default
+    /// question.
+    pub(crate) fn set_question_template_checked(&mut self, question_template: Option<String>) {
+        require!(
+            question_template
+                .as_ref()
+                .is_none_or(|template| template.contains("{champion}")),
+            "ERR_INVALID_QUESTION_TEMPLATE"
+        );
+        self.question_template = question_template;
+    }
+
+    /// `question_template` with `{champion}` filled in, "What beats
+    /// <champion>?" without a template.
+    pub(crate) fn question_for(&self, champion: &str) -> String {
+        match &self.question_template {
+            Some(template) => template.replace("{champion}", champion),
+            None => format!("What beats {}?", champion),
+        }
+    }
+}
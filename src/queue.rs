@@ -0,0 +1,106 @@
+use crate::*;
+
+pub(crate) const DEFAULT_MAX_QUEUE_LENGTH: u64 = 100;
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_max_open_requests(&self) -> Option<u64> {
+        self.max_open_requests
+    }
+
+    pub fn set_max_open_requests(&mut self, max_open_requests: Option<u64>) {
+        self.assert_owner();
+        self.max_open_requests = max_open_requests;
+    }
+
+    pub fn get_max_queue_length(&self) -> u64 {
+        self.max_queue_length
+    }
+
+    pub fn set_max_queue_length(&mut self, max_queue_length: u64) {
+        self.assert_owner();
+        self.max_queue_length = max_queue_length;
+    }
+
+    pub fn get_queue_length(&self) -> u64 {
+        self.queue_tail - self.queue_head - self.queue_cancelled
+    }
+
+    /// Request waiting at `position`, as logged by `request`, with the
+    /// deposit held for it. Queued deposits have no escrow entry until the
+    /// request is promoted.
+    pub fn get_queued_request(&self, position: u64) -> Option<NewRequest> {
+        self.queued_requests.get(&position)
+    }
+
+    /// Withdraws the caller's request waiting at `position` and refunds its
+    /// whole deposit.
+    pub fn cancel_queued_request(&mut self, position: u64) {
+        self.assert_maintenance();
+        let queued = self.queued_requests.get(&position).expect("ERR_NOT_QUEUED");
+        require!(
+            queued.originator_id == env::predecessor_account_id(),
+            "ERR_NOT_REQUEST_OWNER"
+        );
+
+        self.queued_requests.remove(&position);
+        self.queue_cancelled += 1;
+        self.remove_agent_request(&queued.agent);
+        self.distribute_deposit(&queued.originator_id, queued.deposit, &Outcome::Unresolved);
+        log!(
+            "Player {} cancelled the request queued at position {}",
+            queued.originator_id,
+            position
+        );
+    }
+
+    /// Turns up to `max` queued requests into open ones, oldest first, while
+    /// capacity allows. Each one reserves `yield_callback_gas` of the attached
+    /// gas for its yield, plus the trophy mint gas while trophies are enabled.
+    /// Cancelled positions are skipped. Returns the number of requests
+    /// promoted.
+    pub fn promote_queued(&mut self, max: u64) -> u64 {
+        self.assert_operator();
+
+        let mut promoted = 0;
+        while promoted < max && self.queue_head < self.queue_tail && !self.is_overloaded() {
+            let queued = self.queued_requests.remove(&self.queue_head);
+            self.queue_head += 1;
+            let Some(queued) = queued else {
+                self.queue_cancelled -= 1;
+                continue;
+            };
+            self.remove_agent_request(&queued.agent);
+
+            self.create_request(queued);
+            promoted += 1;
+        }
+
+        promoted
+    }
+}
+
+impl Contract {
+    pub(crate) fn is_overloaded(&self) -> bool {
+        self.max_open_requests
+            .is_some_and(|max_open_requests| self.requests.len() >= max_open_requests)
+    }
+
+    /// No yield exists for a queued request until the operator promotes it,
+    /// so the player's own transaction completes right away and the ruling
+    /// only resolves within the promoting transaction.
+    pub(crate) fn enqueue_request(&mut self, queued: NewRequest) {
+        require!(
+            self.get_queue_length() < self.max_queue_length,
+            "ERR_QUEUE_FULL"
+        );
+        log!(
+            "Player {} request queued at position {}",
+            queued.originator_id,
+            self.queue_tail
+        );
+        self.add_agent_request(&queued.agent);
+        self.queued_requests.insert(&self.queue_tail, &queued);
+        self.queue_tail += 1;
+    }
+}
@@ -0,0 +1,54 @@
+use crate::*;
+
+/// Largest configurable `repeat_guess_window`, bounding the per-account buffer.
+const MAX_REPEAT_GUESS_WINDOW: u64 = 20;
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_repeat_guess_window(&self) -> u64 {
+        self.repeat_guess_window
+    }
+
+    /// Number of latest guesses remembered per account for the
+    /// `reject_repeat_guesses` flag, `0` stops tracking.
+    pub fn set_repeat_guess_window(&mut self, window: u64) {
+        self.assert_owner();
+        self.set_repeat_guess_window_checked(window);
+    }
+
+    pub fn get_recent_guesses(&self, account_id: AccountId) -> Vec<String> {
+        self.recent_guesses
+            .get(&account_id)
+            .map(Vec::from)
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    pub(crate) fn set_repeat_guess_window_checked(&mut self, window: u64) {
+        require!(
+            window <= MAX_REPEAT_GUESS_WINDOW,
+            "ERR_REPEAT_GUESS_WINDOW_TOO_LARGE"
+        );
+        self.repeat_guess_window = window;
+    }
+
+    /// Rejects `message` if the flag is on and it is among the account's
+    /// remembered guesses, then remembers it, evicting the oldest one.
+    pub(crate) fn check_repeat_guess(&mut self, account_id: &AccountId, message: &String) {
+        if self.repeat_guess_window == 0 {
+            return;
+        }
+        let mut guesses = self.recent_guesses.get(account_id).unwrap_or_default();
+        require!(
+            !self.feature_flags.reject_repeat_guesses || !guesses.contains(message),
+            "ERR_REPEATED_GUESS"
+        );
+
+        guesses.push_back(message.clone());
+        while guesses.len() as u64 > self.repeat_guess_window {
+            guesses.pop_front();
+        }
+        self.recent_guesses.insert(account_id, &guesses);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::*;
+
+/// Open requests `start_new_season` cancels in one call, more must be swept
+/// or removed beforehand.
+const MAX_SEASON_CANCELS: usize = 50;
+
+/// Everything a new season replaces, see `start_new_season`.
+#[derive(Deserialize, JsonSchema)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize))]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeasonConfig {
+    pub agent_name: String,
+    pub agent_public_key: String,
+    #[serde(default)]
+    pub agent_key_type: Option<KeyType>,
+    pub agent_system_prompt: String,
+    /// Replaces the `get_question` wording, must contain `{champion}`.
+    /// `None` restores "What beats <champion>?".
+    #[serde(default)]
+    pub question_template: Option<String>,
+    pub initial_champion: String,
+    /// Defaults to the contract account itself, as in `new`.
+    #[serde(default)]
+    #[schemars(with = "Option<String>")]
+    pub initial_champion_owner: Option<AccountId>,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_season(&self) -> u64 {
+        self.season
+    }
+
+    pub fn get_question_template(&self) -> Option<String> {
+        self.question_template.clone()
+    }
+
+    /// Moves to a new season in one transaction. Every open request is
+    /// cancelled first, refunding its deposit, then the primary agent and
+    /// question are replaced and `initial_champion` is crowned without
+    /// counting as a conquest. Past champions, history and stats are kept as
+    /// the archive of earlier seasons. Requires an empty queue and no bracket
+    /// in progress. Requests whose ruling was already submitted resolve
+    /// normally and only crown a winner if their champion still holds.
+    pub fn start_new_season(&mut self, config: SeasonConfig) {
+        self.assert_owner();
+        require!(!config.agent_name.is_empty(), "ERR_EMPTY_AGENT_NAME");
+        require!(
+            self.agents.get(&config.agent_name).is_none(),
+            "ERR_AGENT_REGISTERED"
+        );
+        let agent_key_type = config.agent_key_type.unwrap_or_default();
+        require!(
+            is_valid_public_key(&config.agent_public_key, agent_key_type),
+            "ERR_INVALID_PUBLIC_KEY"
+        );
+        let champion = config.initial_champion.to_lowercase();
+        assert!(is_valid_string(champion.as_str()), "Illegal input string");
+        require!(
+            champion.len() as u32 >= self.min_champion_len,
+            "ERR_CHAMPION_TOO_SHORT"
+        );
+        require!(self.get_queue_length() == 0, "ERR_QUEUE_NOT_EMPTY");
+        require!(
+            self.bracket
+                .as_ref()
+                .is_none_or(|bracket| bracket.winner().is_some()),
+            "ERR_BRACKET_IN_PROGRESS"
+        );
+
+        let open_requests: Vec<(RequestId, Request)> = self
+            .requests
+            .iter()
+            .filter(|(request_id, _)| !self.responses.contains_key(request_id))
+            .collect();
+        require!(
+            open_requests.len() <= MAX_SEASON_CANCELS,
+            "ERR_TOO_MANY_REQUESTS_TO_CANCEL"
+        );
+        for (request_id, request) in &open_requests {
+            self.close_request(*request_id, request, cancel_response());
+        }
+
+        self.agent_name = config.agent_name;
+        self.agent_public_key = config.agent_public_key;
+        self.agent_key_type = agent_key_type;
+        self.agent_system_prompt = config.agent_system_prompt;
+        self.last_prompt_change_block = env::block_height();
+        self.set_question_template_checked(config.question_template);
+        self.consecutive_agent_errors = 0;
+
+        self.end_reign();
+        self.all_champions.insert(&champion);
+        self.current_champion = champion;
+        self.champion_owner = config
+            .initial_champion_owner
+            .unwrap_or_else(env::current_account_id);
+        self.champion_since_block = env::block_height();
+        self.champion_reason = None;
+        self.immune_until_block = 0;
+        self.recently_crowned.clear();
+        self.record_champion(None);
+
+        self.season += 1;
+        events::emit::season_started(
+            self.event_forwarder.as_ref(),
+            self.season,
+            &self.current_champion,
+            &self.agent_name,
+            open_requests.len() as u64,
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use crate::config::UpdateConfig;
+use crate::*;
+
+/// Complete game state for `seed_game`. The last `history` record becomes
+/// the current champion.
+#[derive(Deserialize, JsonSchema)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize))]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeedData {
+    pub history: Vec<ChampionRecord>,
+    #[serde(default)]
+    #[schemars(with = "Vec<(String, PlayerStats)>")]
+    pub player_stats: Vec<(AccountId, PlayerStats)>,
+    #[serde(default)]
+    pub config: Option<UpdateConfig>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Reproduces a game state in one call for staging and demos. Allowed
+    /// once, while paused and before any request was made. The records
+    /// continue the history written by `new` in block order, each replacing
+    /// the one before it; `config` is applied as by `update_config`.
+    pub fn seed_game(&mut self, seed: SeedData) {
+        self.assert_owner();
+        require!(self.paused, "ERR_NOT_PAUSED");
+        require!(!self.seeded, "ERR_ALREADY_SEEDED");
+        require!(self.num_requests == 0, "ERR_GAME_STARTED");
+        require!(
+            seed.history.len() as u64 <= MAX_IMPORT_BATCH
+                && seed.player_stats.len() as u64 <= MAX_IMPORT_BATCH,
+            "ERR_SEED_TOO_LARGE"
+        );
+        let champion = seed.history.last().expect("ERR_EMPTY_HISTORY").clone();
+        for (_, stats) in &seed.player_stats {
+            require!(stats.wins <= stats.attempts, "ERR_BAD_PLAYER_STATS");
+        }
+
+        self.seeded = true;
+        if let Some(config) = seed.config {
+            self.update_config(config);
+        }
+
+        self.append_champion_records(&seed.history);
+        self.current_champion = champion.champion.to_lowercase();
+        self.champion_owner = champion.owner_id;
+        self.champion_since_block = champion.block_height;
+        self.champion_reason = None;
+        require!(
+            self.current_champion.len() as u32 >= self.min_champion_len,
+            "ERR_CHAMPION_TOO_SHORT"
+        );
+
+        for (account_id, stats) in &seed.player_stats {
+            self.player_stats.insert(account_id, stats);
+        }
+
+        log!(
+            "Seeded {} champion records and {} players",
+            seed.history.len(),
+            seed.player_stats.len()
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use crate::*;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone, Default)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct PlayerStats {
+    pub attempts: u64,
+    pub wins: u64,
+    pub last_active_block: BlockHeight,
+    /// Awarded by the agent on top of wins, see `max_bonus_points`.
+    #[serde(default)]
+    pub bonus_points: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone, Default)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct ChampionStats {
+    pub attempts: u64,
+    pub wins: u64,
+}
+
+/// Per-unit weights of `get_dominance_score`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct DominanceWeights {
+    pub reign_block: u64,
+    pub hold_rate_bps: u64,
+    pub challenger_defeated: u64,
+}
+
+impl Default for DominanceWeights {
+    fn default() -> Self {
+        Self {
+            reign_block: 1,
+            hold_rate_bps: 1,
+            challenger_defeated: 1,
+        }
+    }
+}
+
+/// Agent rulings applied since deployment. A win that landed after its
+/// champion was dethroned counts as a loss.
+#[derive(Serialize, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OutcomeCounts {
+    pub wins: u64,
+    pub losses: u64,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+pub enum SortKey {
+    Wins,
+    Attempts,
+}
+
+/// Number of most recent judged attempts `get_difficulty` looks at.
+pub(crate) const DIFFICULTY_WINDOW: usize = 100;
+/// Inclusive upper bounds in blocks of the agent latency histogram buckets.
+pub(crate) const LATENCY_BUCKETS: [u64; 7] = [5, 10, 20, 50, 100, 200, u64::MAX];
+
+#[near_bindgen]
+impl Contract {
+    /// Share of the last `DIFFICULTY_WINDOW` judged attempts that won, in
+    /// basis points. Lower values mean the game is currently harder.
+    pub fn get_difficulty(&self) -> u32 {
+        if self.recent_outcomes.is_empty() {
+            return 0;
+        }
+        let wins = self.recent_outcomes.iter().filter(|won| **won).count();
+        (wins * 10_000 / self.recent_outcomes.len()) as u32
+    }
+
+    /// Share of judged guesses against `word` as champion that were
+    /// ruled to beat it, in basis points. `None` if it was never challenged.
+    pub fn get_champion_win_rate(&self, word: String) -> Option<u32> {
+        self.champion_stats
+            .get(&word.to_lowercase())
+            .filter(|stats| stats.attempts > 0)
+            .map(|stats| (stats.wins * 10_000 / stats.attempts) as u32)
+    }
+
+    /// Headline strength of the current champion: its reign length in
+    /// blocks, the basis points of judged guesses it held against and
+    /// the number of those guesses, each times its `DominanceWeights` weight.
+    /// An unchallenged champion has no hold rate and scores on reign alone.
+    pub fn get_dominance_score(&self) -> u64 {
+        let weights = &self.dominance_weights;
+        let reign_blocks = env::block_height() - self.champion_since_block;
+        let stats = self
+            .champion_stats
+            .get(&self.current_champion)
+            .unwrap_or_default();
+        let hold_rate_bps = self
+            .get_champion_win_rate(self.current_champion.clone())
+            .map_or(0, |win_rate| 10_000 - win_rate as u64);
+        let defeated = stats.attempts - stats.wins;
+
+        reign_blocks
+            .saturating_mul(weights.reign_block)
+            .saturating_add(hold_rate_bps.saturating_mul(weights.hold_rate_bps))
+            .saturating_add(defeated.saturating_mul(weights.challenger_defeated))
+    }
+
+    pub fn get_max_bonus_points(&self) -> u64 {
+        self.max_bonus_points
+    }
+
+    /// Most bonus points a single agent response may award, zero disables
+    /// awards. Responses over the cap are rejected as a whole.
+    pub fn set_max_bonus_points(&mut self, max_bonus_points: u64) {
+        self.assert_owner();
+        self.max_bonus_points = max_bonus_points;
+    }
+
+    pub fn get_dominance_weights(&self) -> DominanceWeights {
+        self.dominance_weights.clone()
+    }
+
+    pub fn set_dominance_weights(&mut self, weights: DominanceWeights) {
+        self.assert_owner();
+        self.dominance_weights = weights;
+    }
+
+    /// Distinct accounts that challenged `word` while it was champion, in
+    /// order of their first challenge.
+    pub fn get_challengers(
+        &self,
+        word: String,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<AccountId> {
+        let Some(challengers) = self.challengers.get(&word.to_lowercase()) else {
+            return vec![];
+        };
+        let challengers = challengers.as_vector();
+        let from_index = from_index.unwrap_or(0);
+
+        (from_index
+            ..std::cmp::min(
+                from_index.saturating_add(page_limit(limit)),
+                challengers.len(),
+            ))
+            .filter_map(|index| challengers.get(index))
+            .collect()
+    }
+
+    /// Agent responses by blocks from request creation to `respond`, as
+    /// `(bucket upper bound, count)`. Timeouts and cancellations are excluded.
+    pub fn get_latency_histogram(&self) -> Vec<(u64, u64)> {
+        LATENCY_BUCKETS
+            .iter()
+            .copied()
+            .zip(self.latency_counts.iter().copied())
+            .collect()
+    }
+
+    pub fn get_outcome_counts(&self) -> OutcomeCounts {
+        OutcomeCounts {
+            wins: self.total_wins,
+            losses: self.total_losses,
+        }
+    }
+
+    pub fn get_player_stats(&self, account_id: AccountId) -> Option<PlayerStats> {
+        self.player_stats.get(&account_id)
+    }
+
+    /// Players ranked by `sort_by`, descending. The stats map is unordered,
+    /// so every call reads and sorts all players before paging: view gas
+    /// grows with the player count and becomes a limit past a few thousand.
+    pub fn get_leaderboard(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+        sort_by: SortKey,
+    ) -> Vec<(AccountId, PlayerStats)> {
+        let mut players: Vec<(AccountId, PlayerStats)> = self.player_stats.iter().collect();
+        players.sort_by_key(|(_, stats)| {
+            std::cmp::Reverse(match sort_by {
+                SortKey::Wins => (stats.wins, stats.attempts),
+                SortKey::Attempts => (stats.attempts, stats.wins),
+            })
+        });
+
+        players
+            .into_iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(page_limit(limit) as usize)
+            .collect()
+    }
+
+    /// Drops the stats of up to `max` players without a judged attempt in the
+    /// last `older_than` blocks and returns how many were pruned. Finding them
+    /// reads the whole stats map, like `get_leaderboard`.
+    pub fn prune_inactive_players(&mut self, older_than: u64, max: u64) -> u64 {
+        self.assert_owner();
+
+        let cutoff = env::block_height().saturating_sub(older_than);
+        let inactive: Vec<AccountId> = self
+            .player_stats
+            .iter()
+            .filter(|(_, stats)| stats.last_active_block < cutoff)
+            .map(|(account_id, _)| account_id)
+            .take(max as usize)
+            .collect();
+
+        for account_id in &inactive {
+            self.player_stats.remove(account_id);
+        }
+        inactive.len() as u64
+    }
+}
+
+impl Contract {
+    /// Records a judged attempt, whether ruled by the agent or a known answer.
+    pub(crate) fn record_result(&mut self, account_id: &AccountId, won: bool) {
+        if self.recent_outcomes.len() == DIFFICULTY_WINDOW {
+            self.recent_outcomes.pop_front();
+        }
+        self.recent_outcomes.push_back(won);
+
+        let mut stats = self.player_stats.get(account_id).unwrap_or_default();
+        stats.attempts += 1;
+        if won {
+            stats.wins += 1;
+        }
+        stats.last_active_block = env::block_height();
+        self.player_stats.insert(account_id, &stats);
+    }
+
+    pub(crate) fn record_latency(&mut self, request: &Request) {
+        let latency = env::block_height() - request.created_block;
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| latency <= *bound)
+            .unwrap();
+        self.latency_counts[bucket] += 1;
+    }
+
+    pub(crate) fn record_challenger(&mut self, account_id: &AccountId) {
+        let mut challengers = self
+            .challengers
+            .get(&self.current_champion)
+            .unwrap_or_else(|| {
+                UnorderedSet::new(StorageKey::ChallengersInner {
+                    word_hash: env::sha256_array(self.current_champion.as_bytes()),
+                })
+            });
+        if challengers.insert(account_id) {
+            self.challengers
+                .insert(&self.current_champion, &challengers);
+        }
+    }
+
+    pub(crate) fn award_bonus_points(&mut self, account_id: &AccountId, bonus_points: u64) {
+        if bonus_points == 0 {
+            return;
+        }
+        let mut stats = self.player_stats.get(account_id).unwrap_or_default();
+        stats.bonus_points = stats.bonus_points.saturating_add(bonus_points);
+        self.player_stats.insert(account_id, &stats);
+    }
+
+    /// Counts an agent ruling against `champion`, even if it was dethroned
+    /// before the ruling landed.
+    pub(crate) fn record_champion_result(&mut self, champion: &String, won: bool) {
+        let mut stats = self.champion_stats.get(champion).unwrap_or_default();
+        stats.attempts += 1;
+        if won {
+            stats.wins += 1;
+        }
+        self.champion_stats.insert(champion, &stats);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::*;
+
+/// Decides which of several winning rulings against the same champion takes
+/// the title. Rulings tie while they are all submitted and none has resolved
+/// yet, as when they resolve in the same block; the losers resolve as
+/// `Outcome::Superseded`.
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    Clone,
+    Copy,
+    PartialEq,
+    Default,
+)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum TieBreak {
+    /// Whichever ruling resolves first.
+    #[default]
+    FirstResolved,
+    LowestRequestId,
+    /// Largest escrowed deposit, the lowest request id among equal ones.
+    HighestDeposit,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    pub fn set_tie_break(&mut self, tie_break: TieBreak) {
+        self.assert_owner();
+        self.tie_break = tie_break;
+    }
+}
+
+impl Contract {
+    /// Notes a submitted ruling that, once resolved, would dethrone the
+    /// champion of `request`.
+    pub(crate) fn record_pending_win(
+        &mut self,
+        request_id: RequestId,
+        request: &Request,
+        response: &Response,
+    ) {
+        if request.bracket_match.is_some() || !is_winning_ruling(request, response) {
+            return;
+        }
+        let mut pending_wins = self
+            .pending_wins
+            .get(&request.champion_at_request)
+            .unwrap_or_default();
+        pending_wins.push(request_id);
+        self.pending_wins
+            .insert(&request.champion_at_request, &pending_wins);
+    }
+
+    pub(crate) fn remove_pending_win(&mut self, request_id: RequestId, request: &Request) {
+        let Some(mut pending_wins) = self.pending_wins.get(&request.champion_at_request) else {
+            return;
+        };
+        pending_wins.retain(|pending| *pending != request_id);
+        if pending_wins.is_empty() {
+            self.pending_wins.remove(&request.champion_at_request);
+        } else {
+            self.pending_wins
+                .insert(&request.champion_at_request, &pending_wins);
+        }
+    }
+
+    /// Whether another pending win against the same champion takes priority
+    /// over `request_id` under `tie_break`. Called once the request itself
+    /// is no longer pending.
+    pub(crate) fn is_outranked(&self, request_id: RequestId, request: &Request) -> bool {
+        let pending_wins = self
+            .pending_wins
+            .get(&request.champion_at_request)
+            .unwrap_or_default();
+        let deposit = self.get_escrow(request_id);
+
+        pending_wins.iter().any(|rival| match self.tie_break {
+            TieBreak::FirstResolved => false,
+            TieBreak::LowestRequestId => *rival < request_id,
+            TieBreak::HighestDeposit => {
+                let rival_deposit = self.get_escrow(*rival);
+                rival_deposit > deposit || (rival_deposit == deposit && *rival < request_id)
+            }
+        })
+    }
+}
+
+fn is_winning_ruling(request: &Request, response: &Response) -> bool {
+    response.ok
+        && response
+            .data
+            .as_deref()
+            .and_then(|data| serde_json::from_str::<ResponseMsg>(data).ok())
+            .is_some_and(|ruling| {
+                ruling.guess_wins && ruling.current_champion == request.champion_at_request
+            })
+}
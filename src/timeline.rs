@@ -0,0 +1,57 @@
+use crate::*;
+
+/// Block heights at which a request reached each stage of its lifecycle,
+/// `None` for stages it has not reached. Kept after the request resolves.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, JsonSchema, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct RequestTimeline {
+    pub created: BlockHeight,
+    /// Latest `run_agent` emission, moved forward by `reassign_requests`.
+    pub agent_notified: BlockHeight,
+    pub acked: Option<BlockHeight>,
+    /// Agent response accepted by `respond`.
+    pub responded: Option<BlockHeight>,
+    /// Ruling applied, or the request timed out or was cancelled.
+    pub resolved: Option<BlockHeight>,
+    /// Gas `request` had used once the yield was created, to calibrate the
+    /// gas attached next time against `get_min_prepaid_gas`. `None` for
+    /// requests opened from the queue.
+    #[schemars(with = "Option<String>")]
+    pub request_gas_used: Option<Gas>,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_request_timeline(&self, request_id: RequestId) -> Option<RequestTimeline> {
+        self.timelines.get(&request_id)
+    }
+}
+
+impl Contract {
+    pub(crate) fn start_timeline(&mut self, request_id: RequestId) {
+        self.timelines.insert(
+            &request_id,
+            &RequestTimeline {
+                created: env::block_height(),
+                agent_notified: env::block_height(),
+                acked: None,
+                responded: None,
+                resolved: None,
+                request_gas_used: None,
+            },
+        );
+    }
+
+    /// Applies `update` to the timeline of `request_id`, if it has one.
+    pub(crate) fn update_timeline(
+        &mut self,
+        request_id: RequestId,
+        update: impl FnOnce(&mut RequestTimeline),
+    ) {
+        if let Some(mut timeline) = self.timelines.get(&request_id) {
+            update(&mut timeline);
+            self.timelines.insert(&request_id, &timeline);
+        }
+    }
+}
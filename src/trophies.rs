@@ -0,0 +1,88 @@
+use crate::*;
+use near_contract_standards::non_fungible_token::metadata::TokenMetadata;
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::{ext_contract, is_promise_success};
+
+const TROPHY_MINT_GAS: Gas = Gas::from_tgas(10);
+const ON_TROPHY_MINTED_GAS: Gas = Gas::from_tgas(5);
+
+#[allow(dead_code)]
+#[ext_contract(ext_nft)]
+pub trait ExtNft {
+    fn nft_mint(
+        &mut self,
+        token_id: TokenId,
+        token_owner_id: AccountId,
+        token_metadata: TokenMetadata,
+    );
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Enables trophy minting on `nft_contract`, or disables it with `None`.
+    /// The contract attaches `mint_deposit` to cover the NFT storage.
+    pub fn set_trophy_config(&mut self, nft_contract: Option<AccountId>, mint_deposit: NearToken) {
+        self.assert_owner();
+        self.set_trophy_nft_contract_checked(nft_contract);
+        self.trophy_mint_deposit = mint_deposit;
+    }
+
+    pub fn get_trophy_contract(&self) -> Option<AccountId> {
+        self.trophy_nft_contract.clone()
+    }
+
+    #[private]
+    pub fn on_trophy_minted(&mut self, token_id: TokenId) {
+        if !is_promise_success() {
+            log!("Failed to mint trophy {}", token_id);
+        }
+    }
+}
+
+impl Contract {
+    /// Gas `mint_trophy` attaches from within `await_response`, zero while
+    /// trophies are disabled.
+    pub(crate) fn trophy_mint_gas(&self) -> Gas {
+        match self.trophy_nft_contract {
+            Some(_) => TROPHY_MINT_GAS.saturating_add(ON_TROPHY_MINTED_GAS),
+            None => Gas::from_gas(0),
+        }
+    }
+
+    /// Raises `min_prepaid_gas` when enabling trophies, so requests that
+    /// could not fund the mint are still rejected upfront.
+    pub(crate) fn set_trophy_nft_contract_checked(&mut self, nft_contract: Option<AccountId>) {
+        self.trophy_nft_contract = nft_contract;
+        self.min_prepaid_gas = std::cmp::max(self.min_prepaid_gas, self.request_gas_floor());
+    }
+
+    /// Mints a trophy for the champion just crowned, whichever way it was
+    /// crowned. Fire-and-forget: a failed mint never rolls back the champion
+    /// change.
+    pub(crate) fn mint_trophy(&mut self, reason: &str) {
+        if let Some(nft_contract) = &self.trophy_nft_contract {
+            let token_id: TokenId = self.num_trophies.to_string();
+            self.num_trophies += 1;
+
+            ext_nft::ext(nft_contract.clone())
+                .with_static_gas(TROPHY_MINT_GAS)
+                .with_attached_deposit(self.trophy_mint_deposit)
+                .nft_mint(
+                    token_id.clone(),
+                    self.champion_owner.clone(),
+                    TokenMetadata {
+                        title: Some(self.current_champion.clone()),
+                        description: Some(reason.to_string()),
+                        issued_at: Some(env::block_timestamp_ms().to_string()),
+                        ..Default::default()
+                    },
+                )
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(ON_TROPHY_MINTED_GAS)
+                        .on_trophy_minted(token_id),
+                )
+                .detach();
+        }
+    }
+}
@@ -5,6 +5,20 @@ impl Contract {
         assert!(!self.paused, "Contact paused");
     }
 
+    /// Maintenance blocks player-facing mutations while views and operator
+    /// resolution of already open requests keep working.
+    pub(crate) fn assert_maintenance(&self) {
+        assert!(!self.maintenance, "Contract in maintenance");
+    }
+
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "ERR_NOT_AN_OWNER"
+        );
+    }
+
     pub(crate) fn assert_operator(&self) {
         assert_eq!(
             env::predecessor_account_id(),
@@ -12,12 +26,153 @@ impl Contract {
             "ERR_NOT_AN_OPERATOR"
         );
     }
+
+    pub(crate) fn assert_owner_or_operator(&self) {
+        let predecessor = env::predecessor_account_id();
+        require!(
+            predecessor == self.owner_id || predecessor == self.operator_id,
+            "ERR_NOT_AN_OWNER_OR_OPERATOR"
+        );
+    }
 }
 
 pub(crate) fn remaining_gas() -> Gas {
     Gas::from_gas(env::prepaid_gas().as_gas() - env::used_gas().as_gas())
 }
 
+/// Parses a NEAR formatted `<curve>:<base58>` public key of the given type.
+fn parse_public_key(public_key: &str, key_type: KeyType) -> Option<PublicKey> {
+    let public_key: PublicKey = public_key.parse().ok()?;
+    let curve_type = match key_type {
+        KeyType::Ed25519 => CurveType::ED25519,
+        KeyType::Secp256k1 => CurveType::SECP256K1,
+    };
+    (public_key.curve_type() == curve_type).then_some(public_key)
+}
+
+pub(crate) fn is_valid_public_key(public_key: &str, key_type: KeyType) -> bool {
+    parse_public_key(public_key, key_type).is_some()
+}
+
+/// Verifies a base58 `signature` over `message` against a NEAR formatted
+/// public key of type `key_type`. Ed25519 signatures are 64 bytes, optionally
+/// `ed25519:` prefixed. Secp256k1 signatures are 65 bytes `r || s || v`,
+/// optionally `secp256k1:` prefixed, over the keccak256 hash of `message`.
+pub(crate) fn verify_signature(
+    public_key: &str,
+    key_type: KeyType,
+    message: &[u8],
+    signature: &str,
+) -> bool {
+    let public_key = match parse_public_key(public_key, key_type) {
+        Some(public_key) => public_key,
+        None => return false,
+    };
+    let key_bytes = &public_key.as_bytes()[1..];
+
+    match key_type {
+        KeyType::Ed25519 => {
+            let public_key: [u8; 32] = match key_bytes.try_into() {
+                Ok(public_key) => public_key,
+                Err(_) => return false,
+            };
+            let signature = signature.strip_prefix("ed25519:").unwrap_or(signature);
+            let signature: [u8; 64] = match decode_signature(signature) {
+                Some(signature) => signature,
+                None => return false,
+            };
+
+            env::ed25519_verify(&signature, message, &public_key)
+        }
+        KeyType::Secp256k1 => {
+            let signature = signature.strip_prefix("secp256k1:").unwrap_or(signature);
+            let signature: [u8; 65] = match decode_signature(signature) {
+                Some(signature) => signature,
+                None => return false,
+            };
+
+            env::ecrecover(
+                &env::keccak256_array(message),
+                &signature[..64],
+                signature[64],
+                true,
+            )
+            .is_some_and(|recovered| recovered[..] == *key_bytes)
+        }
+    }
+}
+
+fn decode_signature<const N: usize>(signature: &str) -> Option<[u8; N]> {
+    near_sdk::bs58::decode(signature)
+        .into_vec()
+        .ok()?
+        .try_into()
+        .ok()
+}
+
+/// Derived from the block random seed and the id, so requests created in the
+/// same block still get distinct challenges.
+pub(crate) fn request_challenge(request_id: RequestId) -> String {
+    let mut seed = env::random_seed();
+    seed.extend_from_slice(&request_id.to_le_bytes());
+    near_sdk::bs58::encode(env::sha256_array(&seed)).into_string()
+}
+
+pub(crate) fn page_limit(limit: Option<u64>) -> u64 {
+    std::cmp::min(limit.unwrap_or(DEFAULT_PAGE_LIMIT), MAX_VIEW_LIMIT)
+}
+
+/// `ipfs://<cid>` or `ar://<id>` with an alphanumeric identifier, optionally
+/// followed by a path.
+pub(crate) fn is_valid_content_reference(reference: &str) -> bool {
+    let Some(id) = reference
+        .strip_prefix("ipfs://")
+        .or_else(|| reference.strip_prefix("ar://"))
+    else {
+        return false;
+    };
+    let cid = id.split('/').next().unwrap_or_default();
+
+    reference.len() <= MAX_CONTENT_REFERENCE_LEN
+        && !cid.is_empty()
+        && cid
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && id.chars().all(|c| c.is_ascii_graphic())
+}
+
 pub(crate) fn is_valid_string(input: &str) -> bool {
     input.chars().all(|c| c.is_ascii_lowercase())
 }
+
+/// Classic dynamic programming edit distance over chars, using a single row.
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous_diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_diagonal + usize::from(a_char != *b_char);
+            previous_diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Loose BCP 47 check: a 2-3 letter language followed by `-` separated
+/// alphanumeric subtags of 1-8 characters, at most 35 characters overall.
+pub(crate) fn is_valid_locale(locale: &str) -> bool {
+    let mut subtags = locale.split('-');
+    let language = subtags.next().unwrap_or_default();
+
+    locale.len() <= 35
+        && (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use ed25519_dalek::SigningKey;
+
+fn judge_key() -> SigningKey {
+    SigningKey::from_bytes(&[11; 32])
+}
+
+fn register_judge(contract: &mut Contract) {
+    call_as(&owner());
+    contract.register_agent(
+        "judge".to_string(),
+        public_key_of(&judge_key()),
+        None,
+        "You are a strict judge.".to_string(),
+    );
+}
+
+fn ask_judge(contract: &mut Contract, message: &str) -> u64 {
+    call_as(&alice());
+    let request_id = contract.get_next_request_id();
+    contract.request(
+        message.to_string(),
+        Some("judge".to_string()),
+        None,
+        None,
+        None,
+    );
+    request_id
+}
+
+fn judge_ruling(contract: &Contract, request_id: u64, guess_wins: bool) -> contract::Response {
+    let data = ruling_data(contract, request_id, guess_wins);
+    response_signed_by(&judge_key(), contract, request_id, data.to_string())
+}
+
+#[test]
+fn registered_agent_rules_with_its_own_key() {
+    let mut contract = new_game();
+    register_judge(&mut contract);
+    let request_id = ask_judge(&mut contract, "paper");
+
+    assert!(!contract.verify_response(request_id, ruling(&contract, request_id, true)));
+    let response = judge_ruling(&contract, request_id, true);
+    assert!(contract.verify_response(request_id, response.clone()));
+    respond(&mut contract, request_id, response);
+    resolve(&mut contract, request_id);
+
+    assert_eq!(contract.get_champion(), "paper");
+}
+
+#[test]
+#[should_panic(expected = "ERR_AGENT_IN_USE")]
+fn agent_with_open_requests_cannot_be_unregistered() {
+    let mut contract = new_game();
+    register_judge(&mut contract);
+    ask_judge(&mut contract, "paper");
+
+    call_as(&owner());
+    contract.unregister_agent("judge".to_string());
+}
+
+#[test]
+fn agent_is_unregistered_once_its_requests_resolve() {
+    let mut contract = new_game();
+    register_judge(&mut contract);
+    let request_id = ask_judge(&mut contract, "paper");
+    assert_eq!(contract.get_agent_request_count("judge".to_string()), 1);
+
+    let response = judge_ruling(&contract, request_id, false);
+    respond(&mut contract, request_id, response);
+    resolve(&mut contract, request_id);
+    assert_eq!(contract.get_agent_request_count("judge".to_string()), 0);
+
+    call_as(&owner());
+    contract.unregister_agent("judge".to_string());
+    assert!(contract.get_agent("judge".to_string()).is_none());
+}
+
+#[test]
+fn reassigned_requests_move_their_agent_count() {
+    let mut contract = new_game();
+    register_judge(&mut contract);
+    ask_judge(&mut contract, "paper");
+
+    call_as(&owner());
+    contract.reassign_requests(10, Some("agent".to_string()));
+    contract.unregister_agent("judge".to_string());
+
+    assert_eq!(contract.get_agent_request_count("agent".to_string()), 1);
+}
+
+#[test]
+#[should_panic(expected = "ERR_UNKNOWN_AGENT")]
+fn requests_cannot_target_unknown_agents() {
+    let mut contract = new_game();
+    ask_judge(&mut contract, "paper");
+}
+
+/// Agent payloads of the `run_agent` events the last call emitted.
+fn run_agent_events() -> Vec<near_sdk::serde_json::Value> {
+    events("run_agent")
+        .into_iter()
+        .map(|event| event["data"][0].clone())
+        .collect()
+}
+
+#[test]
+fn reassign_re_emits_unanswered_requests_only() {
+    let mut contract = new_game();
+    let answered = guess(&mut contract, &alice(), "paper");
+    for word in ["water", "cloth", "scissors"] {
+        guess(&mut contract, &bob(), word);
+    }
+    let response = ruling(&contract, answered, false);
+    respond(&mut contract, answered, response);
+
+    call_as(&owner());
+    assert_eq!(contract.reassign_requests(2, None), 2);
+    let events = run_agent_events();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|event| event["request_id"] != answered));
+    assert!(events.iter().all(|event| event["agent"] == "agent"));
+
+    call_as(&owner());
+    assert_eq!(contract.reassign_requests(10, None), 3);
+    assert_eq!(contract.get_requests().len(), 4);
+}
+
+#[test]
+fn reassign_to_another_agent_changes_the_request() {
+    let mut contract = new_game();
+    register_judge(&mut contract);
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    call_as(&owner());
+    contract.reassign_requests(10, Some("judge".to_string()));
+
+    assert_eq!(run_agent_events()[0]["agent"], "judge");
+    assert_eq!(request_json(&contract, request_id)["agent"], "judge");
+    let response = judge_ruling(&contract, request_id, true);
+    respond(&mut contract, request_id, response);
+    resolve(&mut contract, request_id);
+    assert_eq!(contract.get_champion(), "paper");
+}
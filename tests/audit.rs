@@ -0,0 +1,124 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::{self, json, Value};
+
+/// `(message, champion_at_request, outcome)` of each history entry.
+fn history(
+    contract: &Contract,
+    account: &near_sdk::AccountId,
+    from_index: Option<u64>,
+) -> Vec<Value> {
+    contract
+        .get_account_history(account.clone(), from_index, None)
+        .into_iter()
+        .map(|event| {
+            let event = serde_json::to_value(event).unwrap();
+            json!([
+                event["message"],
+                event["champion_at_request"],
+                event["outcome"]
+            ])
+        })
+        .collect()
+}
+
+#[test]
+fn account_history_lists_only_that_players_requests() {
+    let mut contract = new_game();
+    for (account, word, guess_wins) in [
+        (alice(), "paper", true),
+        (bob(), "feather", false),
+        (alice(), "cloud", false),
+    ] {
+        let request_id = guess(&mut contract, &account, word);
+        judge(&mut contract, request_id, guess_wins);
+    }
+    guess(&mut contract, &alice(), "scissors");
+
+    assert_eq!(
+        history(&contract, &alice(), None),
+        vec![
+            json!(["paper", "rock", "Won"]),
+            json!(["cloud", "paper", "Lost"]),
+        ]
+    );
+    assert_eq!(
+        history(&contract, &alice(), Some(1)),
+        vec![json!(["cloud", "paper", "Lost"])]
+    );
+    assert_eq!(history(&contract, &bob(), None).len(), 1);
+    assert!(history(&contract, &carol(), None).is_empty());
+}
+
+#[test]
+fn responses_log_pages_over_every_resolution() {
+    let mut contract = new_game();
+    let words = ["feather", "cloud", "paper", "smoke", "glue"];
+    for (index, word) in words.iter().enumerate() {
+        let request_id = guess(&mut contract, &alice(), word);
+        judge(&mut contract, request_id, index == 2);
+    }
+
+    let messages = |from_index, limit| -> Vec<String> {
+        contract
+            .get_responses_log(from_index, limit)
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect()
+    };
+    assert_eq!(messages(None, None), words);
+    assert_eq!(messages(Some(1), Some(2)), ["cloud", "paper"]);
+    assert_eq!(messages(Some(4), Some(10)), ["glue"]);
+    assert!(messages(Some(5), None).is_empty());
+}
+
+#[test]
+fn responses_chunks_stream_to_completion() {
+    let mut contract = new_game();
+    for _ in 0..120 {
+        let request_id = guess(&mut contract, &bob(), "feather");
+        judge(&mut contract, request_id, false);
+    }
+
+    let mut cursor = None;
+    let mut chunk_sizes = vec![];
+    let mut request_ids = vec![];
+    loop {
+        let chunk = contract.get_responses_chunk(cursor);
+        chunk_sizes.push(chunk.items.len());
+        request_ids.extend(chunk.items.iter().map(|entry| entry.request_id));
+        cursor = chunk.next;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    assert_eq!(chunk_sizes, vec![50, 50, 20]);
+    assert_eq!(request_ids, (0..120).collect::<Vec<u64>>());
+}
+
+#[test]
+#[should_panic(expected = "ERR_BAD_CURSOR")]
+fn malformed_cursor_is_rejected() {
+    let contract = new_game();
+
+    contract.get_responses_chunk(Some("next".to_string()));
+}
+
+#[test]
+fn agent_signature_is_kept_after_resolution() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    assert!(contract.get_response_signature(request_id).is_none());
+
+    let response = ruling(&contract, request_id, true);
+    let signature = response.signature.clone();
+    respond(&mut contract, request_id, response);
+    resolve(&mut contract, request_id);
+
+    assert!(signature.is_some());
+    assert_eq!(contract.get_response_signature(request_id), signature);
+    assert!(contract.get_response_signature(request_id + 1).is_none());
+}
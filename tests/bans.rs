@@ -0,0 +1,53 @@
+mod common;
+
+use common::*;
+
+#[test]
+#[should_panic(expected = "ERR_ACCOUNT_BANNED")]
+fn banned_account_cannot_request() {
+    let mut contract = new_game();
+    call_as(&operator());
+    contract.ban_account(alice());
+
+    guess(&mut contract, &alice(), "paper");
+}
+
+#[test]
+fn unbanned_account_plays_again() {
+    let mut contract = new_game();
+    contract.ban_account(alice());
+    contract.ban_account(bob());
+    assert!(contract.is_banned(alice()));
+    assert_eq!(
+        contract.get_banned_accounts(None, None),
+        vec![alice(), bob()]
+    );
+
+    contract.unban_account(alice());
+    guess(&mut contract, &alice(), "paper");
+
+    assert!(!contract.is_banned(alice()));
+    assert_eq!(contract.get_banned_accounts(None, None), vec![bob()]);
+    assert_eq!(contract.get_requests().len(), 1);
+}
+
+#[test]
+fn ban_leaves_open_requests_to_resolve() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    call_as(&owner());
+    contract.ban_account(alice());
+
+    judge(&mut contract, request_id, true);
+
+    assert_eq!(contract.get_champion(), "paper");
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_AN_OWNER_OR_OPERATOR")]
+fn players_cannot_ban() {
+    let mut contract = new_game();
+
+    call_as(&bob());
+    contract.ban_account(alice());
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::json;
+
+fn award(contract: &mut Contract, word: &str, guess_wins: bool, bonus_points: u64) -> u64 {
+    let request_id = guess(contract, &alice(), word);
+    let mut data = ruling_data(contract, request_id, guess_wins);
+    data["bonus_points"] = json!(bonus_points);
+    let response = signed_response(contract, request_id, data.to_string());
+    respond(contract, request_id, response);
+    resolve(contract, request_id);
+    request_id
+}
+
+fn bonus_points(contract: &Contract) -> u64 {
+    contract.get_player_stats(alice()).unwrap().bonus_points
+}
+
+#[test]
+fn bonus_points_within_the_cap_are_added() {
+    let mut contract = new_game();
+    contract.set_max_bonus_points(5);
+
+    award(&mut contract, "feather", false, 2);
+    award(&mut contract, "paper", true, 5);
+
+    assert_eq!(bonus_points(&contract), 7);
+    assert_eq!(contract.get_champion(), "paper");
+}
+
+#[test]
+fn ruling_over_the_cap_is_rejected_as_a_whole() {
+    let mut contract = new_game();
+    contract.set_max_bonus_points(5);
+    award(&mut contract, "feather", false, 1);
+
+    let request_id = award(&mut contract, "paper", true, 6);
+
+    assert_eq!(bonus_points(&contract), 1);
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert_eq!(
+        contract.get_request_error(request_id).as_deref(),
+        Some("Bonus points over the cap")
+    );
+}
+
+#[test]
+fn awards_are_disabled_by_default() {
+    let mut contract = new_game();
+
+    let request_id = award(&mut contract, "paper", true, 1);
+
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert!(contract.get_request_error(request_id).is_some());
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+
+/// Plays every match of the bracket, the challenger winning when `wins`
+/// says so, and returns the number of matches played.
+fn play_out(contract: &mut Contract, wins: impl Fn(&str, &str) -> bool) -> usize {
+    let mut matches = 0;
+    while contract.get_bracket().unwrap().winner().is_none() {
+        call_as(&operator());
+        let request_id = contract.get_next_request_id();
+        contract.play_bracket_match();
+
+        let request = request_json(contract, request_id);
+        let challenger = request["message"].as_str().unwrap();
+        let defender = request["champion_at_request"].as_str().unwrap();
+        judge(contract, request_id, wins(challenger, defender));
+        matches += 1;
+    }
+    matches
+}
+
+fn create_bracket(contract: &mut Contract, words: &[&str]) {
+    call_as(&owner());
+    contract.create_bracket(words.iter().map(|word| word.to_string()).collect());
+}
+
+#[test]
+fn four_word_bracket_plays_every_round() {
+    let mut contract = new_game();
+    create_bracket(&mut contract, &["paper", "scissors", "lizard", "spock"]);
+
+    let matches = play_out(&mut contract, |_, _| false);
+
+    let bracket = contract.get_bracket().unwrap();
+    assert_eq!(matches, 3);
+    assert_eq!(
+        bracket.rounds,
+        vec![
+            vec!["paper", "scissors", "lizard", "spock"],
+            vec!["paper", "lizard"],
+            vec!["paper"],
+        ]
+    );
+    assert_eq!(bracket.winner().map(String::as_str), Some("paper"));
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+}
+
+#[test]
+fn five_word_bracket_advances_byes_to_the_final() {
+    let mut contract = new_game();
+    create_bracket(
+        &mut contract,
+        &["paper", "scissors", "lizard", "spock", "fire"],
+    );
+
+    let matches = play_out(&mut contract, |_, _| true);
+
+    let bracket = contract.get_bracket().unwrap();
+    assert_eq!(matches, 4);
+    assert_eq!(
+        bracket.rounds,
+        vec![
+            vec!["paper", "scissors", "lizard", "spock", "fire"],
+            vec!["scissors", "spock", "fire"],
+            vec!["spock", "fire"],
+            vec!["fire"],
+        ]
+    );
+    assert_eq!(bracket.winner().map(String::as_str), Some("fire"));
+}
+
+#[test]
+#[should_panic(expected = "ERR_BRACKET_IN_PROGRESS")]
+fn bracket_with_pending_matches_cannot_be_replaced() {
+    let mut contract = new_game();
+    create_bracket(&mut contract, &["paper", "scissors", "lizard", "spock"]);
+
+    call_as(&operator());
+    let request_id = contract.get_next_request_id();
+    contract.play_bracket_match();
+    judge(&mut contract, request_id, true);
+    assert!(contract.get_bracket().unwrap().winner().is_none());
+
+    create_bracket(&mut contract, &["paper", "scissors"]);
+}
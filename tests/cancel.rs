@@ -0,0 +1,45 @@
+mod common;
+
+use common::*;
+
+#[test]
+fn cancels_up_to_max_of_the_callers_requests() {
+    let mut contract = new_game();
+    for word in ["paper", "water", "cloth"] {
+        guess_with(&mut contract, &alice(), word, near(1));
+    }
+    let bobs = guess(&mut contract, &bob(), "scissors");
+
+    call_as(&alice());
+    assert_eq!(contract.cancel_my_requests(2), 2);
+    assert_eq!(transferred_to(&alice()), near(2));
+    assert_eq!(contract.get_requests().len(), 2);
+
+    call_as(&alice());
+    assert_eq!(contract.cancel_my_requests(10), 1);
+    let open: Vec<u64> = contract.get_requests().iter().map(|(id, _)| *id).collect();
+    assert_eq!(open, vec![bobs]);
+}
+
+#[test]
+fn requests_already_answered_are_not_cancelled() {
+    let mut contract = new_game();
+    let answered = guess(&mut contract, &alice(), "paper");
+    guess(&mut contract, &alice(), "water");
+    let response = ruling(&contract, answered, true);
+    respond(&mut contract, answered, response);
+
+    call_as(&alice());
+    assert_eq!(contract.cancel_my_requests(10), 1);
+
+    resolve(&mut contract, answered);
+    assert_eq!(contract.get_champion(), "paper");
+}
+
+#[test]
+fn nothing_to_cancel_returns_zero() {
+    let mut contract = new_game();
+
+    call_as(&alice());
+    assert_eq!(contract.cancel_my_requests(10), 0);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+
+fn judge_with_canonical(
+    contract: &mut Contract,
+    request_id: u64,
+    guess_wins: bool,
+    canonical: &str,
+) {
+    let mut data = ruling_data(contract, request_id, guess_wins);
+    data["canonical_champion"] = canonical.into();
+    let response = signed_response(contract, request_id, data.to_string());
+    respond(contract, request_id, response);
+    resolve(contract, request_id);
+}
+
+#[test]
+fn canonical_form_is_crowned_instead_of_the_guess() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "papers");
+
+    judge_with_canonical(&mut contract, request_id, true, "Paper");
+
+    assert_eq!(contract.get_champion(), "paper");
+    assert!(contract.get_all_champions().contains(&"paper".to_string()));
+}
+
+#[test]
+fn invalid_canonical_form_fails_the_request() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    judge_with_canonical(&mut contract, request_id, true, "pa!per");
+
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert_eq!(
+        contract.get_request_error(request_id).as_deref(),
+        Some("Illegal canonical champion")
+    );
+}
+
+#[test]
+fn canonical_form_is_ignored_on_a_loss() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    judge_with_canonical(&mut contract, request_id, false, "pa!per");
+
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert!(contract.get_request_error(request_id).is_none());
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::*;
+use contract::Response;
+use near_sdk::serde_json;
+
+/// Winning ruling signed over `challenge` instead of the stored one, in
+/// the legacy payload without a challenge when it is `None`.
+fn ruling_with_challenge(
+    contract: &contract::Contract,
+    request_id: u64,
+    challenge: Option<String>,
+) -> Response {
+    let data = Some(ruling_data(contract, request_id, true).to_string());
+    let payload = match &challenge {
+        Some(_) => near_sdk::borsh::to_vec(&(request_id, true, &data, &challenge)).unwrap(),
+        None => near_sdk::borsh::to_vec(&(request_id, true, &data)).unwrap(),
+    };
+
+    Response {
+        ok: true,
+        data,
+        signature: Some(sign(&payload)),
+        challenge,
+        originator: None,
+    }
+}
+
+#[test]
+fn each_request_gets_its_own_challenge() {
+    let mut contract = new_game();
+    let first = guess(&mut contract, &alice(), "paper");
+    let second = guess(&mut contract, &bob(), "water");
+
+    assert_ne!(
+        request_json(&contract, first)["challenge"],
+        request_json(&contract, second)["challenge"]
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_CHALLENGE_MISMATCH")]
+fn respond_rejects_a_response_for_another_challenge() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let response = ruling_with_challenge(&contract, request_id, Some("presigned".to_string()));
+
+    respond(&mut contract, request_id, response);
+}
+
+fn require_challenge(contract: &mut contract::Contract) {
+    call_as(&owner());
+    let mut flags = serde_json::to_value(contract.get_feature_flags()).unwrap();
+    flags["require_challenge"] = true.into();
+    contract.set_feature_flags(serde_json::from_value(flags).unwrap());
+}
+
+#[test]
+fn responses_without_a_challenge_are_accepted_by_default() {
+    let mut contract = new_game();
+    assert!(!contract.get_feature_flags().require_challenge);
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    let response = ruling_with_challenge(&contract, request_id, None);
+    assert!(contract.verify_response(request_id, response.clone()));
+    respond(&mut contract, request_id, response);
+    resolve(&mut contract, request_id);
+
+    assert_eq!(contract.get_champion(), "paper");
+}
+
+#[test]
+fn verify_response_flags_a_missing_challenge() {
+    let mut contract = new_game();
+    require_challenge(&mut contract);
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    let response = ruling_with_challenge(&contract, request_id, None);
+    assert!(!contract.verify_response(request_id, response));
+    assert!(contract.verify_response(request_id, ruling(&contract, request_id, true)));
+}
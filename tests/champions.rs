@@ -0,0 +1,48 @@
+mod common;
+
+use common::*;
+
+#[test]
+fn champions_are_paged_alphabetically() {
+    let mut contract = new_game();
+    for word in ["paper", "scissors", "hammer", "anvil"] {
+        let request_id = guess(&mut contract, &alice(), word);
+        judge(&mut contract, request_id, true);
+    }
+
+    assert_eq!(
+        contract.get_champions_sorted(None, None),
+        vec!["anvil", "hammer", "paper", "rock", "scissors"]
+    );
+    assert_eq!(
+        contract.get_champions_sorted(Some(1), Some(2)),
+        vec!["hammer", "paper"]
+    );
+    assert!(contract.get_champions_sorted(Some(5), None).is_empty());
+}
+
+#[test]
+fn champions_are_searched_by_prefix() {
+    let mut contract = new_game();
+    for word in ["paper", "parrot", "scissors", "pan"] {
+        let request_id = guess(&mut contract, &alice(), word);
+        judge(&mut contract, request_id, true);
+    }
+
+    let mut matches = contract.search_champions("PA".to_string(), None, None);
+    matches.sort();
+    assert_eq!(matches, vec!["pan", "paper", "parrot"]);
+    assert_eq!(
+        contract
+            .search_champions("pa".to_string(), Some(1), Some(1))
+            .len(),
+        1
+    );
+    assert_eq!(
+        contract.search_champions("sc".to_string(), None, None),
+        vec!["scissors"]
+    );
+    assert!(contract
+        .search_champions("stone".to_string(), None, None)
+        .is_empty());
+}
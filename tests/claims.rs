@@ -0,0 +1,99 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+
+fn claim(contract: &mut Contract) -> Option<u64> {
+    call_as(&operator());
+    contract
+        .claim_next_request()
+        .map(|(request_id, _)| request_id)
+}
+
+#[test]
+fn workers_claim_distinct_requests_oldest_first() {
+    let mut contract = new_game();
+    let first = guess(&mut contract, &alice(), "paper");
+    let second = guess(&mut contract, &bob(), "water");
+
+    assert_eq!(claim(&mut contract), Some(first));
+    assert_eq!(claim(&mut contract), Some(second));
+    assert_eq!(claim(&mut contract), None);
+}
+
+#[test]
+fn answered_requests_are_not_claimed() {
+    let mut contract = new_game();
+    let answered = guess(&mut contract, &alice(), "paper");
+    let open = guess(&mut contract, &bob(), "water");
+    let response = ruling(&contract, answered, false);
+    respond(&mut contract, answered, response);
+
+    assert_eq!(claim(&mut contract), Some(open));
+}
+
+#[test]
+fn expired_lease_makes_the_request_claimable_again() {
+    let mut contract = new_game();
+    contract.set_claim_lease_blocks(10);
+    let request_id = guess(&mut contract, &alice(), "paper");
+    assert_eq!(claim(&mut contract), Some(request_id));
+
+    advance_blocks(9);
+    assert_eq!(claim(&mut contract), None);
+
+    advance_blocks(1);
+    assert_eq!(claim(&mut contract), Some(request_id));
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_AN_OPERATOR")]
+fn only_the_operator_claims_requests() {
+    let mut contract = new_game();
+    guess(&mut contract, &alice(), "paper");
+
+    call_as(&alice());
+    contract.claim_next_request();
+}
+
+fn game_requiring_ack() -> Contract {
+    let mut contract = new_game();
+    let mut flags = near_sdk::serde_json::to_value(contract.get_feature_flags()).unwrap();
+    flags["require_ack"] = true.into();
+    contract.set_feature_flags(near_sdk::serde_json::from_value(flags).unwrap());
+    contract
+}
+
+#[test]
+#[should_panic(expected = "ERR_REQUEST_NOT_ACKED")]
+fn respond_requires_an_ack_when_enabled() {
+    let mut contract = game_requiring_ack();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let response = ruling(&contract, request_id, true);
+
+    respond(&mut contract, request_id, response);
+}
+
+#[test]
+fn acked_request_accepts_the_response() {
+    let mut contract = game_requiring_ack();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    assert!(!contract.verify_response(request_id, ruling(&contract, request_id, true)));
+
+    call_as(&operator());
+    contract.ack_request(request_id);
+    assert_eq!(request_json(&contract, request_id)["acked"], true);
+    judge(&mut contract, request_id, true);
+
+    assert_eq!(contract.get_champion(), "paper");
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_AN_OPERATOR")]
+fn only_the_operator_acks_requests() {
+    let mut contract = game_requiring_ack();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    call_as(&alice());
+    contract.ack_request(request_id);
+}
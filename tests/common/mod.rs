@@ -0,0 +1,237 @@
+#![allow(dead_code)]
+
+use contract::{Contract, Response};
+use ed25519_dalek::{Signer, SigningKey};
+use near_sdk::mock::MockAction;
+use near_sdk::serde_json::{self, json, Value};
+use near_sdk::test_utils::{get_created_receipts, get_logs, VMContextBuilder};
+use near_sdk::{env, testing_env, AccountId, Gas, GasWeight, NearToken, PromiseOrValue};
+
+pub const INITIAL_CHAMPION: &str = "rock";
+/// Block height the game is created at.
+pub const START_BLOCK: u64 = 1_000;
+
+pub fn game() -> AccountId {
+    "game.near".parse().unwrap()
+}
+
+pub fn owner() -> AccountId {
+    "owner.near".parse().unwrap()
+}
+
+pub fn operator() -> AccountId {
+    "operator.near".parse().unwrap()
+}
+
+pub fn alice() -> AccountId {
+    "alice.near".parse().unwrap()
+}
+
+pub fn bob() -> AccountId {
+    "bob.near".parse().unwrap()
+}
+
+pub fn carol() -> AccountId {
+    "carol.near".parse().unwrap()
+}
+
+pub fn near(amount: u128) -> NearToken {
+    NearToken::from_near(amount)
+}
+
+pub fn agent_key() -> SigningKey {
+    SigningKey::from_bytes(&[7; 32])
+}
+
+pub fn public_key_of(key: &SigningKey) -> String {
+    format!(
+        "ed25519:{}",
+        near_sdk::bs58::encode(key.verifying_key().as_bytes()).into_string()
+    )
+}
+
+pub fn agent_public_key() -> String {
+    public_key_of(&agent_key())
+}
+
+pub fn sign_with(key: &SigningKey, message: &[u8]) -> String {
+    near_sdk::bs58::encode(key.sign(message).to_bytes()).into_string()
+}
+
+pub fn sign(message: &[u8]) -> String {
+    sign_with(&agent_key(), message)
+}
+
+/// Replaces the mocked context, keeping storage.
+pub fn set_context(account: &AccountId, deposit: NearToken, block_height: u64) {
+    testing_env!(VMContextBuilder::new()
+        .current_account_id(game())
+        .predecessor_account_id(account.clone())
+        .signer_account_id(account.clone())
+        .attached_deposit(deposit)
+        .prepaid_gas(Gas::from_tgas(300))
+        .block_height(block_height)
+        .build());
+}
+
+pub fn call_as(account: &AccountId) {
+    call_with(account, NearToken::from_yoctonear(0));
+}
+
+pub fn call_with(account: &AccountId, deposit: NearToken) {
+    set_context(account, deposit, env::block_height());
+}
+
+pub fn advance_blocks(blocks: u64) {
+    set_context(
+        &env::predecessor_account_id(),
+        env::attached_deposit(),
+        env::block_height() + blocks,
+    );
+}
+
+pub fn new_game() -> Contract {
+    set_context(&owner(), NearToken::from_yoctonear(0), START_BLOCK);
+    Contract::new(
+        owner(),
+        operator(),
+        INITIAL_CHAMPION.to_string(),
+        None,
+        None,
+        "agent".to_string(),
+        "Decide whether the guess beats the champion.".to_string(),
+        agent_public_key(),
+        None,
+    )
+}
+
+/// Places a guess and returns the id of the request it opened.
+pub fn guess(contract: &mut Contract, account: &AccountId, message: &str) -> u64 {
+    guess_with(contract, account, message, NearToken::from_yoctonear(0))
+}
+
+pub fn guess_with(
+    contract: &mut Contract,
+    account: &AccountId,
+    message: &str,
+    deposit: NearToken,
+) -> u64 {
+    call_with(account, deposit);
+    let request_id = contract.get_next_request_id();
+    contract.request(message.to_string(), None, None, None, None);
+    request_id
+}
+
+/// Stored request as JSON, its fields are private to the contract.
+pub fn request_json(contract: &Contract, request_id: u64) -> Value {
+    serde_json::to_value(contract.get_request(request_id)).unwrap()
+}
+
+pub fn data_id(contract: &Contract, request_id: u64) -> [u8; 32] {
+    serde_json::from_value(request_json(contract, request_id)["data_id"].clone()).unwrap()
+}
+
+/// Agent response carrying `data` for `request_id`, signed with `key`.
+pub fn response_signed_by(
+    key: &SigningKey,
+    contract: &Contract,
+    request_id: u64,
+    data: String,
+) -> Response {
+    let request = request_json(contract, request_id);
+    let challenge = request["challenge"].as_str().map(str::to_string);
+    let data = Some(data);
+    let payload = near_sdk::borsh::to_vec(&(request_id, true, &data, &challenge)).unwrap();
+
+    Response {
+        ok: true,
+        data,
+        signature: Some(sign_with(key, &payload)),
+        challenge,
+        originator: None,
+    }
+}
+
+pub fn signed_response(contract: &Contract, request_id: u64, data: String) -> Response {
+    response_signed_by(&agent_key(), contract, request_id, data)
+}
+
+/// Ruling data on `request_id` against the champion it was made against.
+pub fn ruling_data(contract: &Contract, request_id: u64, guess_wins: bool) -> Value {
+    let request = request_json(contract, request_id);
+    json!({
+        "current_champion": request["champion_at_request"],
+        "guess_wins": guess_wins,
+        "reason": "because",
+    })
+}
+
+pub fn ruling(contract: &Contract, request_id: u64, guess_wins: bool) -> Response {
+    let data = ruling_data(contract, request_id, guess_wins);
+    signed_response(contract, request_id, data.to_string())
+}
+
+/// The mocked runtime forgets every yield when the context is replaced, so
+/// yields are recreated until `data_id` is pending again.
+pub fn revive_yield(data_id: [u8; 32]) {
+    for _ in 0..1024 {
+        env::promise_yield_create("await_response", [], Gas::from_tgas(1), GasWeight(0), 0);
+        if env::read_register(0).unwrap() == data_id {
+            return;
+        }
+    }
+    panic!("Yield not found");
+}
+
+pub fn respond(contract: &mut Contract, request_id: u64, response: Response) {
+    call_as(&operator());
+    let data_id = data_id(contract, request_id);
+    revive_yield(data_id);
+    contract.respond(data_id, request_id, response);
+}
+
+/// Runs the callback the resumed yield schedules.
+pub fn resolve(contract: &mut Contract, request_id: u64) -> Response {
+    call_as(&game());
+    match contract.await_response(request_id) {
+        PromiseOrValue::Value(response) => response,
+        PromiseOrValue::Promise(_) => panic!("Expected a value"),
+    }
+}
+
+/// Responds with a ruling and resolves it.
+pub fn judge(contract: &mut Contract, request_id: u64, guess_wins: bool) -> Response {
+    let response = ruling(contract, request_id, guess_wins);
+    respond(contract, request_id, response);
+    resolve(contract, request_id)
+}
+
+/// Total transferred to `account` by the last call.
+pub fn transferred_to(account: &AccountId) -> NearToken {
+    get_created_receipts()
+        .iter()
+        .filter(|receipt| &receipt.receiver_id == account)
+        .flat_map(|receipt| receipt.actions.iter())
+        .fold(NearToken::from_yoctonear(0), |total, action| match action {
+            MockAction::Transfer { deposit, .. } => total.saturating_add(*deposit),
+            _ => total,
+        })
+}
+
+/// Every `name` event the last call emitted, in order.
+pub fn events(name: &str) -> Vec<Value> {
+    get_logs()
+        .iter()
+        .filter_map(|log| log.strip_prefix("EVENT_JSON:"))
+        .map(|event| serde_json::from_str::<Value>(event).unwrap())
+        .filter(|event| event["event"] == name)
+        .collect()
+}
+
+/// First `name` event the last call emitted.
+pub fn event(name: &str) -> Value {
+    events(name)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| panic!("{} not emitted", name))
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::{self, json, Map, Value};
+
+fn update_config(contract: &mut Contract, config: Value) {
+    call_as(&owner());
+    contract.update_config(serde_json::from_value(config).unwrap());
+}
+
+fn config_value(contract: &Contract, key: &str) -> Value {
+    let (_, value) = contract
+        .get_config_kv()
+        .into_iter()
+        .find(|(name, _)| name == key)
+        .unwrap_or_else(|| panic!("{} missing", key));
+    serde_json::from_str(&value).unwrap()
+}
+
+#[test]
+fn config_kv_is_sorted_and_applies_as_a_no_op() {
+    let mut contract = new_game();
+    let entries = contract.get_config_kv();
+    let hash = contract.get_config_hash();
+
+    let mut sorted = entries.clone();
+    sorted.sort();
+    assert_eq!(entries, sorted);
+
+    let config: Map<String, Value> = entries
+        .into_iter()
+        .map(|(key, value)| (key, serde_json::from_str(&value).unwrap()))
+        .collect();
+    update_config(&mut contract, Value::Object(config));
+
+    assert_eq!(contract.get_config_hash(), hash);
+}
+
+#[test]
+fn claim_lease_and_question_template_are_part_of_the_config() {
+    let mut contract = new_game();
+    let hash = contract.get_config_hash();
+
+    update_config(
+        &mut contract,
+        json!({
+            "claim_lease_blocks": 7,
+            "question_template": "Which word defeats {champion}?",
+        }),
+    );
+
+    assert_eq!(contract.get_claim_lease_blocks(), 7);
+    assert_eq!(contract.get_question(), "Which word defeats rock?");
+    assert_eq!(config_value(&contract, "claim_lease_blocks"), json!(7));
+    assert_eq!(
+        config_value(&contract, "question_template"),
+        json!("Which word defeats {champion}?")
+    );
+    assert_ne!(contract.get_config_hash(), hash);
+
+    update_config(&mut contract, json!({ "question_template": null }));
+    assert_eq!(contract.get_question(), "What beats rock?");
+    assert_eq!(config_value(&contract, "question_template"), Value::Null);
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_QUESTION_TEMPLATE")]
+fn question_template_needs_a_champion_placeholder() {
+    let mut contract = new_game();
+
+    update_config(
+        &mut contract,
+        json!({ "question_template": "Which word wins?" }),
+    );
+}
+
+#[test]
+fn partial_update_only_touches_the_given_fields() {
+    let mut contract = new_game();
+    contract.set_decay_blocks(Some(500));
+    let deadline = contract.get_response_deadline_blocks();
+
+    update_config(
+        &mut contract,
+        json!({
+            "min_champion_len": 3,
+            "max_open_requests": 5,
+            "max_queue_length": 10,
+        }),
+    );
+
+    assert_eq!(contract.get_min_champion_len(), 3);
+    assert_eq!(contract.get_max_open_requests(), Some(5));
+    assert_eq!(contract.get_max_queue_length(), 10);
+    assert_eq!(contract.get_decay_blocks(), Some(500));
+    assert_eq!(contract.get_response_deadline_blocks(), deadline);
+}
+
+#[test]
+fn explicit_null_clears_a_nullable_setting() {
+    let mut contract = new_game();
+    update_config(&mut contract, json!({ "max_open_requests": 5 }));
+
+    update_config(&mut contract, json!({ "max_open_requests": null }));
+
+    assert_eq!(contract.get_max_open_requests(), None);
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_AN_OWNER")]
+fn only_the_owner_updates_the_config() {
+    let mut contract = new_game();
+
+    call_as(&operator());
+    contract.update_config(serde_json::from_value(json!({ "min_champion_len": 3 })).unwrap());
+}
+
+#[test]
+fn config_hash_tracks_config_changes() {
+    let mut contract = new_game();
+    let hash = contract.get_config_hash();
+    assert_eq!(contract.get_config_hash(), hash);
+
+    contract.set_min_champion_len(3);
+    let changed = contract.get_config_hash();
+    assert_ne!(changed, hash);
+
+    contract.set_min_champion_len(1);
+    assert_eq!(contract.get_config_hash(), hash);
+
+    let request_id = guess(&mut contract, &alice(), "paper");
+    judge(&mut contract, request_id, true);
+    assert_eq!(contract.get_config_hash(), hash);
+}
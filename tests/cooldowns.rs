@@ -0,0 +1,43 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+
+/// Crowns "paper" with a 50 block crowning cooldown, then "scissors" a
+/// block later.
+fn crown_paper_then_scissors(contract: &mut Contract) {
+    call_as(&owner());
+    contract.set_crowned_cooldown_blocks(50);
+    let request_id = guess(contract, &alice(), "paper");
+    judge(contract, request_id, true);
+    advance_blocks(1);
+    let request_id = guess(contract, &bob(), "scissors");
+    judge(contract, request_id, true);
+}
+
+#[test]
+#[should_panic(expected = "ERR_RECENTLY_CROWNED")]
+fn freshly_crowned_word_cannot_be_guessed() {
+    let mut contract = new_game();
+    crown_paper_then_scissors(&mut contract);
+
+    guess(&mut contract, &carol(), "paper");
+}
+
+#[test]
+fn crowning_cooldown_expires() {
+    let mut contract = new_game();
+    crown_paper_then_scissors(&mut contract);
+    assert_eq!(
+        contract.get_cooldowns(carol()).recently_crowned_remaining,
+        vec![("paper".to_string(), 49), ("scissors".to_string(), 50)]
+    );
+
+    advance_blocks(49);
+    assert_eq!(
+        contract.get_cooldowns(carol()).recently_crowned_remaining,
+        vec![("scissors".to_string(), 1)]
+    );
+    guess(&mut contract, &carol(), "paper");
+    assert_eq!(contract.get_requests().len(), 1);
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::AccountId;
+
+fn crown_signature(contract: &Contract, word: &str, owner: &AccountId, reason: &str) -> String {
+    let payload = near_sdk::borsh::to_vec(&(
+        word,
+        owner,
+        reason,
+        contract.get_champion(),
+        contract.get_crown_nonce(),
+    ))
+    .unwrap();
+    sign(&payload)
+}
+
+#[test]
+fn agent_signed_result_crowns_the_champion() {
+    let mut contract = new_game();
+    let signature = crown_signature(&contract, "paper", &alice(), "paper covers rock");
+
+    call_as(&operator());
+    contract.crown_champion_verified(
+        "paper".to_string(),
+        alice(),
+        signature,
+        "paper covers rock".to_string(),
+    );
+
+    assert_eq!(contract.get_champion(), "paper");
+    assert_eq!(contract.get_champion_owner(), alice());
+    assert_eq!(contract.get_crown_nonce(), 2);
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_SIGNATURE")]
+fn signature_from_another_key_is_rejected() {
+    let mut contract = new_game();
+    let payload = near_sdk::borsh::to_vec(&(
+        "paper",
+        alice(),
+        "paper covers rock",
+        INITIAL_CHAMPION,
+        contract.get_crown_nonce(),
+    ))
+    .unwrap();
+    let signature = sign_with(&ed25519_dalek::SigningKey::from_bytes(&[9; 32]), &payload);
+
+    call_as(&operator());
+    contract.crown_champion_verified(
+        "paper".to_string(),
+        alice(),
+        signature,
+        "paper covers rock".to_string(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_SIGNATURE")]
+fn signature_cannot_be_replayed_after_a_decay() {
+    let mut contract = new_game();
+    call_as(&owner());
+    contract.set_decay_blocks(Some(10));
+    let signature = crown_signature(&contract, "paper", &alice(), "paper covers rock");
+    call_as(&operator());
+    contract.crown_champion_verified(
+        "paper".to_string(),
+        alice(),
+        signature.clone(),
+        "paper covers rock".to_string(),
+    );
+
+    advance_blocks(10);
+    contract.decay_champion();
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+
+    call_as(&operator());
+    contract.crown_champion_verified(
+        "paper".to_string(),
+        alice(),
+        signature,
+        "paper covers rock".to_string(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_AN_OPERATOR")]
+fn only_the_operator_submits_crowns() {
+    let mut contract = new_game();
+    let signature = crown_signature(&contract, "paper", &alice(), "paper covers rock");
+
+    call_as(&alice());
+    contract.crown_champion_verified(
+        "paper".to_string(),
+        alice(),
+        signature,
+        "paper covers rock".to_string(),
+    );
+}
+
+fn crown(contract: &mut Contract, word: &str, owner: &AccountId) {
+    let signature = crown_signature(contract, word, owner, "paper covers rock");
+    call_as(&operator());
+    contract.crown_champion_verified(
+        word.to_string(),
+        owner.clone(),
+        signature,
+        "paper covers rock".to_string(),
+    );
+}
+
+#[test]
+fn crowning_the_reigning_champion_again_is_a_no_op() {
+    let mut contract = new_game();
+    crown(&mut contract, "paper", &alice());
+    advance_blocks(5);
+
+    crown(&mut contract, "paper", &alice());
+
+    let profile = contract.get_current_champion_profile();
+    assert_eq!(profile.reign_count, 1);
+    assert_eq!(profile.since_block, START_BLOCK);
+    assert_eq!(contract.get_crown_nonce(), 2);
+    assert_eq!(
+        contract.get_conquests("paper".to_string(), None, None),
+        vec!["rock"]
+    );
+}
+
+#[test]
+fn same_word_for_a_new_owner_is_a_new_reign() {
+    let mut contract = new_game();
+    crown(&mut contract, "paper", &alice());
+
+    crown(&mut contract, "paper", &bob());
+
+    assert_eq!(contract.get_champion_owner(), bob());
+    assert_eq!(contract.get_current_champion_profile().reign_count, 2);
+    assert_eq!(
+        contract.get_conquests("paper".to_string(), None, None),
+        vec!["rock"]
+    );
+}
+
+#[test]
+fn verified_crown_mints_a_trophy() {
+    let mut contract = new_game();
+    let nft: AccountId = "trophies.near".parse().unwrap();
+    call_as(&owner());
+    contract.set_trophy_config(Some(nft.clone()), near_sdk::NearToken::from_millinear(10));
+    let signature = crown_signature(&contract, "paper", &alice(), "paper covers rock");
+
+    call_as(&operator());
+    contract.crown_champion_verified(
+        "paper".to_string(),
+        alice(),
+        signature,
+        "paper covers rock".to_string(),
+    );
+
+    assert!(near_sdk::test_utils::get_created_receipts()
+        .iter()
+        .any(|receipt| receipt.receiver_id == nft));
+}
@@ -0,0 +1,254 @@
+mod common;
+
+use common::*;
+use near_sdk::{serde_json, NearToken};
+
+/// Default response window.
+const DEADLINE: u64 = 200;
+
+#[test]
+#[should_panic(expected = "ERR_YIELD_EXPIRED")]
+fn respond_fails_once_the_yield_is_gone() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let response = ruling(&contract, request_id, true);
+
+    // Without reviving it, the mocked runtime no longer knows the yield.
+    call_as(&operator());
+    contract.respond(data_id(&contract, request_id), request_id, response);
+}
+
+#[test]
+fn extension_stays_within_the_yield_timeout() {
+    let mut contract = new_game();
+    call_as(&owner());
+    contract.set_response_deadline_blocks(150);
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    call_as(&operator());
+    let deadline = contract.extend_deadline(request_id, 50);
+
+    assert_eq!(deadline, START_BLOCK + 200);
+    let agent_data = serde_json::to_value(contract.agent_data(request_id)).unwrap();
+    assert_eq!(agent_data["response_deadline_block"], deadline);
+}
+
+#[test]
+#[should_panic(expected = "ERR_DEADLINE_PAST_YIELD_TIMEOUT")]
+fn extension_past_the_yield_timeout_is_rejected() {
+    let mut contract = new_game();
+    call_as(&owner());
+    contract.set_response_deadline_blocks(150);
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    call_as(&operator());
+    contract.extend_deadline(request_id, 30);
+    contract.extend_deadline(request_id, 30);
+}
+
+#[test]
+#[should_panic(expected = "ERR_DEADLINE_PAST_YIELD_TIMEOUT")]
+fn response_window_past_the_yield_timeout_is_rejected() {
+    let mut contract = new_game();
+
+    call_as(&owner());
+    contract.set_response_deadline_blocks(201);
+}
+
+/// Funds the prize pool with a lost 1 NEAR deposit from carol.
+fn fund_prize_pool(contract: &mut contract::Contract) {
+    let request_id = guess_with(contract, &carol(), "stone", near(1));
+    judge(contract, request_id, false);
+}
+
+#[test]
+fn keeper_is_paid_for_forcing_a_timeout() {
+    let mut contract = new_game();
+    fund_prize_pool(&mut contract);
+    call_as(&owner());
+    contract.set_keeper_reward(NearToken::from_millinear(100));
+    let pool = contract.get_prize_pool();
+    assert!(pool > NearToken::from_millinear(100));
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    advance_blocks(DEADLINE + 1);
+    call_as(&bob());
+    contract.force_timeout(request_id);
+
+    assert!(contract.get_requests().is_empty());
+    assert_eq!(
+        contract.get_prize_pool(),
+        pool.checked_sub(NearToken::from_millinear(100)).unwrap()
+    );
+}
+
+#[test]
+fn originator_forcing_its_own_timeout_is_not_paid() {
+    let mut contract = new_game();
+    fund_prize_pool(&mut contract);
+    call_as(&owner());
+    contract.set_keeper_reward(NearToken::from_millinear(100));
+    let pool = contract.get_prize_pool();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    advance_blocks(DEADLINE + 1);
+    call_as(&alice());
+    contract.force_timeout(request_id);
+
+    assert!(contract.get_requests().is_empty());
+    assert_eq!(contract.get_prize_pool(), pool);
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_EXPIRED")]
+fn timeout_cannot_be_forced_before_the_deadline() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    advance_blocks(DEADLINE);
+    call_as(&bob());
+    contract.force_timeout(request_id);
+}
+
+#[test]
+fn next_request_refunds_expired_requests() {
+    let mut contract = new_game();
+    let expired = guess_with(&mut contract, &alice(), "paper", near(1));
+
+    advance_blocks(DEADLINE + 1);
+    let request_id = guess(&mut contract, &bob(), "water");
+
+    assert_eq!(transferred_to(&alice()), near(1));
+    let open: Vec<u64> = contract.get_requests().iter().map(|(id, _)| *id).collect();
+    assert_eq!(open, vec![request_id]);
+    assert_eq!(contract.get_escrow(expired), NearToken::from_yoctonear(0));
+}
+
+#[test]
+fn next_request_times_out_at_most_two() {
+    let mut contract = new_game();
+    for word in ["paper", "water", "cloth"] {
+        guess(&mut contract, &alice(), word);
+    }
+
+    advance_blocks(DEADLINE + 1);
+    guess(&mut contract, &bob(), "scissors");
+
+    assert_eq!(contract.get_requests().len(), 2);
+}
+
+#[test]
+fn requests_within_their_deadline_are_kept() {
+    let mut contract = new_game();
+    guess(&mut contract, &alice(), "paper");
+
+    advance_blocks(DEADLINE);
+    guess(&mut contract, &bob(), "water");
+
+    assert_eq!(contract.get_requests().len(), 2);
+}
+
+#[test]
+fn agent_sees_the_response_deadline() {
+    let mut contract = new_game();
+    call_as(&owner());
+    contract.set_response_deadline_blocks(50);
+    advance_blocks(7);
+
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    let deadline = START_BLOCK + 7 + 50;
+    assert_eq!(
+        event("run_agent")["data"][0]["response_deadline_block"],
+        deadline
+    );
+    let agent_data = serde_json::to_value(contract.agent_data(request_id)).unwrap();
+    assert_eq!(agent_data["response_deadline_block"], deadline);
+}
+
+#[test]
+fn logged_deadline_is_when_the_request_expires() {
+    let mut contract = new_game();
+    call_as(&owner());
+    contract.set_response_deadline_blocks(120);
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let deadline = event("run_agent")["data"][0]["response_deadline_block"]
+        .as_u64()
+        .unwrap();
+
+    assert_eq!(deadline, START_BLOCK + 120);
+    advance_blocks(deadline - START_BLOCK);
+    assert!(!contract.is_request_expired(request_id));
+    advance_blocks(1);
+    assert!(contract.is_request_expired(request_id));
+}
+
+#[test]
+fn only_open_requests_can_be_expired() {
+    let mut contract = new_game();
+    let resolved = guess(&mut contract, &alice(), "paper");
+    judge(&mut contract, resolved, false);
+    let open = guess(&mut contract, &bob(), "water");
+
+    advance_blocks(DEADLINE + 1);
+
+    assert!(contract.is_request_expired(open));
+    assert!(!contract.is_request_expired(resolved));
+    assert!(!contract.is_request_expired(open + 1));
+}
+
+#[test]
+fn reclaimed_request_resolves_with_the_configured_response() {
+    let mut contract = new_game();
+    call_as(&owner());
+    contract.set_timeout_response(contract::Response {
+        ok: false,
+        data: Some("timed out".to_string()),
+        signature: Some("ignored".to_string()),
+        challenge: None,
+        originator: None,
+    });
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    advance_blocks(DEADLINE + 1);
+    call_as(&bob());
+    revive_yield(data_id(&contract, request_id));
+    contract.force_timeout(request_id);
+    let response = resolve(&mut contract, request_id);
+
+    assert!(!response.ok);
+    assert_eq!(response.data.as_deref(), Some("timed out"));
+    assert!(response.signature.is_none());
+    assert!(contract.get_requests().is_empty());
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+}
+
+#[test]
+fn sweep_times_out_up_to_max_expired_requests() {
+    let mut contract = new_game();
+    for word in ["paper", "water", "cloth"] {
+        guess(&mut contract, &alice(), word);
+    }
+
+    advance_blocks(DEADLINE + 1);
+    call_as(&bob());
+    assert_eq!(contract.sweep_expired(2), 2);
+    assert_eq!(contract.get_requests().len(), 1);
+    assert_eq!(contract.sweep_expired(10), 1);
+    assert!(contract.get_requests().is_empty());
+}
+
+#[test]
+fn sweep_stops_at_the_first_request_within_its_deadline() {
+    let mut contract = new_game();
+    guess(&mut contract, &alice(), "paper");
+    advance_blocks(50);
+    let fresh = guess(&mut contract, &alice(), "water");
+
+    advance_blocks(DEADLINE - 49);
+    call_as(&bob());
+    assert_eq!(contract.sweep_expired(10), 1);
+
+    let open: Vec<u64> = contract.get_requests().iter().map(|(id, _)| *id).collect();
+    assert_eq!(open, vec![fresh]);
+}
@@ -0,0 +1,27 @@
+mod common;
+
+use common::*;
+use contract::Request;
+use near_sdk::serde_json;
+
+#[test]
+fn request_bytes_round_trip() {
+    let mut contract = new_game();
+    call_as(&alice());
+    let request_id = contract.get_next_request_id();
+    contract.request(
+        "paper".to_string(),
+        None,
+        None,
+        Some("en".to_string()),
+        Some(vec!["object".to_string()]),
+    );
+
+    let bytes = contract.debug_request_bytes(request_id);
+    let decoded: Request = near_sdk::borsh::from_slice(&bytes).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(decoded).unwrap(),
+        request_json(&contract, request_id)
+    );
+}
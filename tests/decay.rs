@@ -0,0 +1,113 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::{self, json};
+use near_sdk::AccountId;
+
+fn crown(contract: &mut Contract, account: &AccountId, word: &str) {
+    let request_id = guess(contract, account, word);
+    judge(contract, request_id, true);
+}
+
+fn enable_decay(contract: &mut Contract) {
+    call_as(&owner());
+    contract.set_decay_blocks(Some(10));
+}
+
+fn decay(contract: &mut Contract) {
+    advance_blocks(10);
+    call_as(&carol());
+    contract.decay_champion();
+}
+
+#[test]
+fn decay_restores_the_replaced_champion() {
+    let mut contract = new_game();
+    enable_decay(&mut contract);
+    crown(&mut contract, &alice(), "paper");
+
+    decay(&mut contract);
+
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert_eq!(
+        contract.get_current_champion_profile().reason.as_deref(),
+        Some("decayed")
+    );
+    assert!(contract
+        .get_conquests(INITIAL_CHAMPION.to_string(), None, None)
+        .is_empty());
+}
+
+#[test]
+fn repeated_decays_walk_back_through_the_history() {
+    let mut contract = new_game();
+    enable_decay(&mut contract);
+    crown(&mut contract, &alice(), "paper");
+    crown(&mut contract, &bob(), "scissors");
+
+    decay(&mut contract);
+    assert_eq!(contract.get_champion(), "paper");
+    assert_eq!(contract.get_champion_owner(), alice());
+
+    decay(&mut contract);
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+}
+
+#[test]
+#[should_panic(expected = "ERR_NO_PREVIOUS_CHAMPION")]
+fn decay_does_not_bring_the_decayed_champion_back() {
+    let mut contract = new_game();
+    enable_decay(&mut contract);
+    crown(&mut contract, &alice(), "paper");
+    decay(&mut contract);
+
+    decay(&mut contract);
+}
+
+#[test]
+fn decay_after_a_new_win_restores_the_previously_restored_champion() {
+    let mut contract = new_game();
+    enable_decay(&mut contract);
+    crown(&mut contract, &alice(), "paper");
+    crown(&mut contract, &bob(), "scissors");
+    decay(&mut contract);
+    crown(&mut contract, &carol(), "stone");
+
+    decay(&mut contract);
+
+    assert_eq!(contract.get_champion(), "paper");
+    assert_eq!(contract.get_champion_owner(), alice());
+}
+
+#[test]
+#[should_panic(expected = "ERR_DECAY_TOO_EARLY")]
+fn decay_waits_for_the_window() {
+    let mut contract = new_game();
+    enable_decay(&mut contract);
+    crown(&mut contract, &alice(), "paper");
+
+    advance_blocks(9);
+    contract.decay_champion();
+}
+
+#[test]
+#[should_panic(expected = "ERR_NO_PREVIOUS_CHAMPION")]
+fn decay_stops_at_the_start_of_the_season() {
+    let mut contract = new_game();
+    enable_decay(&mut contract);
+    crown(&mut contract, &alice(), "paper");
+
+    call_as(&owner());
+    contract.start_new_season(
+        serde_json::from_value(json!({
+            "agent_name": "agent2",
+            "agent_public_key": agent_public_key(),
+            "agent_system_prompt": "Judge the second season.",
+            "initial_champion": "water",
+        }))
+        .unwrap(),
+    );
+
+    decay(&mut contract);
+}
@@ -0,0 +1,259 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::{self, Value};
+use near_sdk::NearToken;
+
+fn zero() -> NearToken {
+    NearToken::from_yoctonear(0)
+}
+
+/// Ten percent of every winning deposit goes to the prize pool.
+fn game_with_win_fee() -> Contract {
+    let mut contract = new_game();
+    contract.set_win_fee_bps(1_000);
+    contract
+}
+
+fn last_outcome(contract: &Contract) -> Value {
+    let log = contract.get_responses_log(None, None);
+    serde_json::to_value(&log.last().unwrap().outcome).unwrap()
+}
+
+/// Caps open requests at one and queues the rest.
+fn game_with_queue() -> Contract {
+    let mut contract = new_game();
+    contract.set_max_open_requests(Some(1));
+    let mut flags = serde_json::to_value(contract.get_feature_flags()).unwrap();
+    flags["queue_when_overloaded"] = true.into();
+    contract.set_feature_flags(serde_json::from_value(flags).unwrap());
+    contract
+}
+
+#[test]
+fn deposit_is_held_while_the_request_is_open() {
+    let mut contract = game_with_win_fee();
+    let request_id = guess_with(&mut contract, &alice(), "paper", near(1));
+
+    assert_eq!(contract.get_escrow(request_id), near(1));
+}
+
+#[test]
+fn win_refunds_the_deposit_minus_the_fee() {
+    let mut contract = game_with_win_fee();
+    let request_id = guess_with(&mut contract, &alice(), "paper", near(1));
+
+    judge(&mut contract, request_id, true);
+
+    assert_eq!(transferred_to(&alice()), NearToken::from_millinear(900));
+    assert_eq!(contract.get_prize_pool(), NearToken::from_millinear(100));
+    assert_eq!(contract.get_escrow(request_id), zero());
+    assert_eq!(last_outcome(&contract), "Won");
+}
+
+#[test]
+fn loss_moves_the_deposit_to_the_pool() {
+    let mut contract = game_with_win_fee();
+    let request_id = guess_with(&mut contract, &alice(), "paper", near(1));
+
+    judge(&mut contract, request_id, false);
+
+    assert_eq!(transferred_to(&alice()), zero());
+    assert_eq!(contract.get_prize_pool(), near(1));
+    assert_eq!(last_outcome(&contract), "Lost");
+}
+
+#[test]
+fn cancellation_refunds_the_whole_deposit() {
+    let mut contract = game_with_win_fee();
+    let request_id = guess_with(&mut contract, &alice(), "paper", near(1));
+
+    call_as(&alice());
+    assert_eq!(contract.cancel_my_requests(10), 1);
+
+    assert_eq!(transferred_to(&alice()), near(1));
+    assert_eq!(contract.get_prize_pool(), zero());
+    assert_eq!(contract.get_escrow(request_id), zero());
+    assert_eq!(last_outcome(&contract), "Unresolved");
+}
+
+#[test]
+fn malformed_ruling_refunds_the_whole_deposit() {
+    let mut contract = game_with_win_fee();
+    let request_id = guess_with(&mut contract, &alice(), "paper", near(1));
+
+    let response = signed_response(&contract, request_id, "not json".to_string());
+    respond(&mut contract, request_id, response);
+    resolve(&mut contract, request_id);
+
+    assert_eq!(transferred_to(&alice()), near(1));
+    assert_eq!(contract.get_prize_pool(), zero());
+    assert_eq!(last_outcome(&contract), "Unresolved");
+}
+
+#[test]
+fn win_against_a_dethroned_champion_is_partially_refunded() {
+    let mut contract = game_with_win_fee();
+    let first = guess_with(&mut contract, &alice(), "paper", near(1));
+    let second = guess_with(&mut contract, &bob(), "water", near(1));
+
+    judge(&mut contract, first, true);
+    judge(&mut contract, second, true);
+
+    assert_eq!(contract.get_current_champion_profile().word, "paper");
+    assert_eq!(transferred_to(&bob()), NearToken::from_millinear(900));
+    assert_eq!(contract.get_prize_pool(), NearToken::from_millinear(200));
+    assert_eq!(last_outcome(&contract), "Superseded");
+}
+
+#[test]
+fn queued_deposit_is_visible_until_promoted() {
+    let mut contract = game_with_queue();
+    guess(&mut contract, &alice(), "paper");
+    guess_with(&mut contract, &bob(), "water", near(1));
+
+    assert_eq!(contract.get_queue_length(), 1);
+    let queued = serde_json::to_value(contract.get_queued_request(0)).unwrap();
+    assert_eq!(queued["originator_id"], "bob.near");
+    assert_eq!(queued["deposit"], near(1).as_yoctonear().to_string());
+}
+
+#[test]
+fn cancelled_queued_request_is_refunded_and_skipped() {
+    let mut contract = game_with_queue();
+    let open = guess(&mut contract, &alice(), "paper");
+    guess_with(&mut contract, &bob(), "water", near(1));
+    let promoted = contract.get_next_request_id();
+    guess(&mut contract, &carol(), "scissors");
+
+    call_as(&bob());
+    contract.cancel_queued_request(0);
+    assert_eq!(transferred_to(&bob()), near(1));
+    assert!(contract.get_queued_request(0).is_none());
+    assert_eq!(contract.get_queue_length(), 1);
+
+    judge(&mut contract, open, false);
+    call_as(&operator());
+    assert_eq!(contract.promote_queued(1), 1);
+
+    assert_eq!(contract.get_queue_length(), 0);
+    assert_eq!(
+        request_json(&contract, promoted)["originator_id"],
+        "carol.near"
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_REQUEST_OWNER")]
+fn only_the_player_cancels_a_queued_request() {
+    let mut contract = game_with_queue();
+    guess(&mut contract, &alice(), "paper");
+    guess_with(&mut contract, &bob(), "water", near(1));
+
+    call_as(&carol());
+    contract.cancel_queued_request(0);
+}
+
+/// Funds the pool with a lost 10 NEAR deposit and pays 0.1 NEAR plus
+/// 1 milliNEAR per reign block for a win.
+fn game_with_win_reward() -> Contract {
+    let mut contract = new_game();
+    contract.set_win_reward(NearToken::from_millinear(100), NearToken::from_millinear(1));
+    let request_id = guess_with(&mut contract, &carol(), "feather", near(10));
+    judge(&mut contract, request_id, false);
+    contract
+}
+
+fn win_after(contract: &mut Contract, blocks: u64) -> NearToken {
+    advance_blocks(blocks);
+    let expected = contract.get_potential_reward();
+    let request_id = guess(contract, &alice(), "paper");
+    judge(contract, request_id, true);
+    assert_eq!(transferred_to(&alice()), expected);
+    expected
+}
+
+#[test]
+fn short_reign_pays_little_over_the_base_reward() {
+    let mut contract = game_with_win_reward();
+
+    assert_eq!(win_after(&mut contract, 10), NearToken::from_millinear(110));
+    assert_eq!(contract.get_prize_pool(), NearToken::from_millinear(9_890));
+}
+
+#[test]
+fn long_reign_pays_more() {
+    let mut contract = game_with_win_reward();
+
+    assert_eq!(
+        win_after(&mut contract, 1_000),
+        NearToken::from_millinear(1_100)
+    );
+}
+
+#[test]
+fn reward_never_exceeds_the_pool() {
+    let mut contract = game_with_win_reward();
+
+    assert_eq!(win_after(&mut contract, 20_000), near(10));
+    assert_eq!(contract.get_prize_pool(), zero());
+}
+
+#[test]
+fn total_collected_accumulates_every_deposit() {
+    let mut contract = game_with_win_fee();
+    let won = guess_with(&mut contract, &alice(), "paper", near(1));
+    judge(&mut contract, won, true);
+    let lost = guess_with(&mut contract, &bob(), "feather", near(2));
+    judge(&mut contract, lost, false);
+    guess_with(&mut contract, &carol(), "water", near(3));
+    guess(&mut contract, &alice(), "cloth");
+
+    assert_eq!(contract.get_total_collected(), near(6));
+    assert_eq!(contract.get_account_deposits(bob()), near(2));
+}
+
+/// Caps every account at 3 NEAR of lifetime deposits.
+fn game_with_deposit_cap() -> Contract {
+    let mut contract = new_game();
+    contract.set_max_total_deposit(Some(near(3)));
+    contract
+}
+
+#[test]
+fn deposits_up_to_the_cap_are_accepted() {
+    let mut contract = game_with_deposit_cap();
+    let request_id = guess_with(&mut contract, &alice(), "paper", near(2));
+    judge(&mut contract, request_id, false);
+
+    guess_with(&mut contract, &alice(), "water", near(1));
+    guess_with(&mut contract, &bob(), "cloth", near(3));
+
+    assert_eq!(contract.get_account_deposits(alice()), near(3));
+    assert_eq!(contract.get_account_deposits(bob()), near(3));
+}
+
+#[test]
+#[should_panic(expected = "ERR_DEPOSIT_CAP_REACHED")]
+fn deposit_past_the_cap_is_rejected() {
+    let mut contract = game_with_deposit_cap();
+    guess_with(&mut contract, &alice(), "paper", near(2));
+
+    guess_with(
+        &mut contract,
+        &alice(),
+        "water",
+        near(1).saturating_add(NearToken::from_yoctonear(1)),
+    );
+}
+
+#[test]
+fn free_requests_pass_at_the_cap() {
+    let mut contract = game_with_deposit_cap();
+    guess_with(&mut contract, &alice(), "paper", near(3));
+
+    guess(&mut contract, &alice(), "water");
+
+    assert_eq!(contract.get_requests().len(), 2);
+}
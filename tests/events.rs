@@ -0,0 +1,84 @@
+mod common;
+
+use common::*;
+use near_sdk::mock::MockAction;
+use near_sdk::serde_json::{self, Value};
+use near_sdk::test_utils::get_created_receipts;
+use near_sdk::{AccountId, Gas};
+
+fn indexer() -> AccountId {
+    "indexer.near".parse().unwrap()
+}
+
+/// `(gas, event)` of every `on_event` call forwarded by the last call.
+fn forwarded_events() -> Vec<(Gas, Value)> {
+    get_created_receipts()
+        .into_iter()
+        .filter(|receipt| receipt.receiver_id == indexer())
+        .flat_map(|receipt| receipt.actions)
+        .filter_map(|action| match action {
+            MockAction::FunctionCallWeight {
+                method_name,
+                args,
+                prepaid_gas,
+                ..
+            } if method_name == b"on_event" => {
+                let args: Value = serde_json::from_slice(&args).unwrap();
+                let event = serde_json::from_str(args["event_json"].as_str().unwrap()).unwrap();
+                Some((prepaid_gas, event))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn events_are_forwarded_with_a_fixed_gas_budget() {
+    let mut contract = new_game();
+    contract.set_event_forwarder(Some(indexer()));
+
+    guess(&mut contract, &alice(), "paper");
+
+    let forwarded = forwarded_events();
+    assert!(!forwarded.is_empty());
+    assert!(forwarded.iter().all(|(gas, _)| *gas == Gas::from_tgas(5)));
+    let (_, run_agent) = &forwarded[0];
+    assert_eq!(run_agent["event"], "run_agent");
+    assert_eq!(*run_agent, event("run_agent"));
+}
+
+#[test]
+fn nothing_is_forwarded_without_a_forwarder() {
+    let mut contract = new_game();
+    contract.set_event_forwarder(Some(indexer()));
+    contract.set_event_forwarder(None);
+
+    guess(&mut contract, &alice(), "paper");
+
+    assert!(forwarded_events().is_empty());
+}
+
+#[test]
+fn request_created_carries_the_full_context() {
+    let mut contract = new_game();
+
+    let request_id = guess(&mut contract, &alice(), "Paper");
+
+    let data_id = serde_json::to_value(data_id(&contract, request_id)).unwrap();
+    assert_eq!(
+        event("request_created"),
+        serde_json::json!({
+            "standard": "nearai",
+            "version": "0.1.0",
+            "event": "request_created",
+            "data": [{
+                "request_id": request_id,
+                "originator_id": "alice.near",
+                "message": "paper",
+                "champion_at_request": "rock",
+                "data_id": data_id,
+                "tags": [],
+            }],
+        })
+    );
+}
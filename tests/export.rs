@@ -0,0 +1,38 @@
+mod common;
+
+use common::*;
+use near_sdk::base64::Engine;
+
+fn decode(blob: &str) -> Vec<String> {
+    let bytes = near_sdk::base64::engine::general_purpose::STANDARD
+        .decode(blob)
+        .unwrap();
+    near_sdk::borsh::from_slice(&bytes).unwrap()
+}
+
+#[test]
+fn export_round_trips_the_champions() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    judge(&mut contract, request_id, true);
+    let request_id = guess(&mut contract, &bob(), "scissors");
+    judge(&mut contract, request_id, true);
+
+    let champions = decode(&contract.export_champions_encoded());
+
+    assert_eq!(champions, contract.get_all_champions());
+    assert_eq!(champions, vec!["rock", "paper", "scissors"]);
+}
+
+#[test]
+fn export_format_is_a_counted_list_of_words() {
+    let contract = new_game();
+    let bytes = near_sdk::base64::engine::general_purpose::STANDARD
+        .decode(contract.export_champions_encoded())
+        .unwrap();
+
+    let mut expected = 1u32.to_le_bytes().to_vec();
+    expected.extend_from_slice(&4u32.to_le_bytes());
+    expected.extend_from_slice(b"rock");
+    assert_eq!(bytes, expected);
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{env, serde_json, testing_env, AccountId, Gas};
+
+fn set_flag(contract: &mut Contract, name: &str, value: bool) {
+    call_as(&owner());
+    let mut flags = serde_json::to_value(contract.get_feature_flags()).unwrap();
+    flags[name] = value.into();
+    contract.set_feature_flags(serde_json::from_value(flags).unwrap());
+}
+
+#[test]
+fn operator_plays_by_default() {
+    let mut contract = new_game();
+
+    let request_id = guess(&mut contract, &operator(), "paper");
+
+    assert_eq!(contract.get_requests()[0].0, request_id);
+}
+
+#[test]
+#[should_panic(expected = "ERR_OPERATOR_CANNOT_PLAY")]
+fn operator_cannot_play_once_disallowed() {
+    let mut contract = new_game();
+    set_flag(&mut contract, "operator_can_play", false);
+
+    guess(&mut contract, &operator(), "paper");
+}
+
+#[test]
+fn players_are_unaffected_when_the_operator_cannot_play() {
+    let mut contract = new_game();
+    set_flag(&mut contract, "operator_can_play", false);
+
+    guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(contract.get_requests().len(), 1);
+}
+
+/// Places a guess relayed by `relayer` on behalf of the signing `account`.
+fn relayed_guess(contract: &mut Contract, relayer: &AccountId, account: &AccountId) {
+    testing_env!(VMContextBuilder::new()
+        .current_account_id(game())
+        .predecessor_account_id(relayer.clone())
+        .signer_account_id(account.clone())
+        .prepaid_gas(Gas::from_tgas(300))
+        .block_height(env::block_height())
+        .build());
+    contract.request("paper".to_string(), None, None, None, None);
+}
+
+#[test]
+fn indirect_calls_are_allowed_by_default() {
+    let mut contract = new_game();
+
+    relayed_guess(&mut contract, &carol(), &alice());
+
+    assert_eq!(contract.get_requests().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "ERR_INDIRECT_CALL")]
+fn indirect_calls_are_rejected_when_direct_calls_are_required() {
+    let mut contract = new_game();
+    set_flag(&mut contract, "require_direct_call", true);
+
+    relayed_guess(&mut contract, &carol(), &alice());
+}
+
+#[test]
+fn direct_calls_pass_when_required() {
+    let mut contract = new_game();
+    set_flag(&mut contract, "require_direct_call", true);
+
+    guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(contract.get_requests().len(), 1);
+}
+
+#[test]
+fn substring_guesses_are_accepted_by_default() {
+    let mut contract = new_game();
+
+    guess(&mut contract, &alice(), "rockslide");
+
+    assert_eq!(contract.get_requests().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "ERR_SUBSTRING_GUESS")]
+fn guess_inside_the_champion_is_rejected() {
+    let mut contract = new_game();
+    set_flag(&mut contract, "reject_substring_guesses", true);
+
+    guess(&mut contract, &alice(), "roc");
+}
+
+#[test]
+#[should_panic(expected = "ERR_SUBSTRING_GUESS")]
+fn guess_containing_the_champion_is_rejected() {
+    let mut contract = new_game();
+    set_flag(&mut contract, "reject_substring_guesses", true);
+
+    guess(&mut contract, &alice(), "rockslide");
+}
+
+#[test]
+fn unrelated_guess_passes_the_substring_check() {
+    let mut contract = new_game();
+    set_flag(&mut contract, "reject_substring_guesses", true);
+
+    guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(contract.get_requests().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "ERR_DUPLICATE_IN_FLIGHT")]
+fn duplicate_in_flight_guess_is_rejected() {
+    let mut contract = new_game();
+    set_flag(&mut contract, "reject_duplicate_in_flight", true);
+    guess(&mut contract, &alice(), "paper");
+
+    guess(&mut contract, &bob(), "Paper");
+}
+
+#[test]
+fn resolved_or_distinct_guesses_are_not_duplicates() {
+    let mut contract = new_game();
+    set_flag(&mut contract, "reject_duplicate_in_flight", true);
+    let request_id = guess(&mut contract, &alice(), "paper");
+    guess(&mut contract, &bob(), "water");
+    judge(&mut contract, request_id, false);
+
+    guess(&mut contract, &bob(), "paper");
+
+    assert_eq!(contract.get_requests().len(), 2);
+}
+
+#[test]
+fn duplicates_are_allowed_by_default() {
+    let mut contract = new_game();
+    guess(&mut contract, &alice(), "paper");
+
+    guess(&mut contract, &bob(), "paper");
+
+    assert_eq!(contract.get_requests().len(), 2);
+}
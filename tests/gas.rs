@@ -0,0 +1,133 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{env, testing_env, Gas, NearToken};
+
+fn guess_with_gas(contract: &mut Contract, message: &str, prepaid_gas: Gas) {
+    testing_env!(VMContextBuilder::new()
+        .current_account_id(game())
+        .predecessor_account_id(alice())
+        .signer_account_id(alice())
+        .prepaid_gas(prepaid_gas)
+        .block_height(env::block_height())
+        .build());
+    contract.request(message.to_string(), None, None, None, None);
+}
+
+#[test]
+fn requests_work_across_prepaid_gas_values() {
+    let mut contract = new_game();
+    let floor = contract.get_min_prepaid_gas();
+    assert_eq!(floor, Gas::from_tgas(80));
+
+    for (tgas, word) in [(80, "paper"), (150, "water"), (300, "cloth")] {
+        guess_with_gas(&mut contract, word, Gas::from_tgas(tgas));
+    }
+
+    assert_eq!(contract.get_requests().len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "ERR_INSUFFICIENT_PREPAID_GAS")]
+fn requests_below_the_floor_are_rejected_upfront() {
+    let mut contract = new_game();
+
+    guess_with_gas(&mut contract, "paper", Gas::from_tgas(79));
+}
+
+#[test]
+fn raising_the_callback_gas_raises_the_floor() {
+    let mut contract = new_game();
+
+    contract.set_yield_callback_gas(Gas::from_tgas(60));
+
+    assert_eq!(contract.get_min_prepaid_gas(), Gas::from_tgas(100));
+}
+
+#[test]
+#[should_panic(expected = "ERR_PREPAID_GAS_TOO_LOW")]
+fn floor_cannot_drop_below_the_request_and_callback_gas() {
+    let mut contract = new_game();
+    set_context(&owner(), NearToken::from_yoctonear(0), START_BLOCK);
+
+    contract.set_min_prepaid_gas(Gas::from_tgas(79));
+}
+
+fn cycle_gas(contract: &Contract, field: &str) -> Gas {
+    let estimate = near_sdk::serde_json::to_value(contract.estimate_cycle_gas()).unwrap();
+    near_sdk::serde_json::from_value(estimate[field].clone()).unwrap()
+}
+
+#[test]
+fn cycle_estimate_follows_the_prepaid_gas_floor() {
+    let mut contract = new_game();
+    let respond_gas = cycle_gas(&contract, "respond_gas");
+    assert_eq!(cycle_gas(&contract, "request_gas"), Gas::from_tgas(80));
+    assert_eq!(
+        cycle_gas(&contract, "total"),
+        Gas::from_tgas(80).saturating_add(respond_gas)
+    );
+
+    contract.set_min_prepaid_gas(Gas::from_tgas(120));
+
+    assert_eq!(cycle_gas(&contract, "request_gas"), Gas::from_tgas(120));
+    assert_eq!(
+        cycle_gas(&contract, "total"),
+        Gas::from_tgas(120).saturating_add(respond_gas)
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_INSUFFICIENT_PREPAID_GAS")]
+fn prepaid_gas_is_checked_before_the_guess() {
+    let mut contract = new_game();
+    contract.set_paused(true);
+
+    guess_with_gas(&mut contract, "not a word!", Gas::from_tgas(10));
+}
+
+#[test]
+fn requests_work_across_callback_gas_values() {
+    let mut contract = new_game();
+
+    for (tgas, word) in [(40, "paper"), (100, "water"), (200, "cloth")] {
+        set_context(&owner(), NearToken::from_yoctonear(0), START_BLOCK);
+        contract.set_yield_callback_gas(Gas::from_tgas(tgas));
+        assert_eq!(contract.get_yield_callback_gas(), Gas::from_tgas(tgas));
+        let floor = contract.get_min_prepaid_gas();
+        assert!(floor >= Gas::from_tgas(tgas + 40));
+
+        guess_with_gas(&mut contract, word, floor);
+    }
+
+    assert_eq!(contract.get_requests().len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "ERR_BAD_YIELD_CALLBACK_GAS")]
+fn callback_gas_below_the_floor_is_rejected() {
+    let mut contract = new_game();
+
+    contract.set_yield_callback_gas(Gas::from_tgas(39));
+}
+
+#[test]
+fn request_reports_the_gas_it_used() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    let gas_used = contract
+        .get_request_timeline(request_id)
+        .unwrap()
+        .request_gas_used
+        .unwrap();
+    assert!(gas_used > Gas::from_gas(0));
+    assert!(gas_used <= env::used_gas());
+    assert!(gas_used < contract.get_min_prepaid_gas());
+    let logged = format!("Request {} used {}", request_id, gas_used);
+    assert!(near_sdk::test_utils::get_logs()
+        .iter()
+        .any(|log| log.starts_with(&logged)));
+}
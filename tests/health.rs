@@ -0,0 +1,80 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+
+fn fail_ruling(contract: &mut Contract, word: &str) {
+    let request_id = guess(contract, &alice(), word);
+    let response = signed_response(contract, request_id, "not json".to_string());
+    respond(contract, request_id, response);
+    resolve(contract, request_id);
+}
+
+#[test]
+fn successful_ruling_resets_the_error_count() {
+    let mut contract = new_game();
+    contract.set_max_consecutive_agent_errors(Some(2));
+
+    fail_ruling(&mut contract, "paper");
+    fail_ruling(&mut contract, "water");
+    assert_eq!(contract.get_consecutive_agent_errors(), 2);
+    let request_id = guess(&mut contract, &bob(), "cloth");
+    judge(&mut contract, request_id, false);
+
+    assert_eq!(contract.get_consecutive_agent_errors(), 0);
+    assert!(!contract.is_paused());
+}
+
+#[test]
+fn too_many_errors_in_a_row_pause_the_game() {
+    let mut contract = new_game();
+    contract.set_max_consecutive_agent_errors(Some(2));
+
+    for word in ["paper", "water", "cloth"] {
+        fail_ruling(&mut contract, word);
+    }
+
+    assert!(contract.is_paused());
+    assert_eq!(event("agent_down")["data"][0]["consecutive_errors"], 3);
+    assert!(contract.get_gating_status().agent_down);
+}
+
+#[test]
+fn errors_never_pause_without_a_threshold() {
+    let mut contract = new_game();
+
+    for word in ["paper", "water", "cloth"] {
+        fail_ruling(&mut contract, word);
+    }
+
+    assert!(!contract.is_paused());
+}
+
+#[test]
+fn gating_status_follows_each_gate() {
+    let mut contract = new_game();
+    let status = contract.get_gating_status();
+    assert!(!status.paused && !status.maintenance && !status.overloaded);
+    assert!(!status.agent_unconfigured && !status.decay_pending);
+
+    let request_id = guess(&mut contract, &alice(), "paper");
+    judge(&mut contract, request_id, true);
+    call_as(&owner());
+    contract.set_decay_blocks(Some(10));
+    advance_blocks(10);
+    assert!(contract.get_gating_status().decay_pending);
+
+    call_as(&owner());
+    contract.set_max_open_requests(Some(1));
+    contract.set_maintenance(true);
+    let status = contract.get_gating_status();
+    assert!(status.maintenance && !status.overloaded);
+    contract.set_maintenance(false);
+    guess(&mut contract, &bob(), "scissors");
+    assert!(contract.get_gating_status().overloaded);
+
+    call_as(&owner());
+    contract.set_paused(true);
+    let status = contract.get_gating_status();
+    assert!(status.paused && !status.agent_down && !status.decay_pending);
+}
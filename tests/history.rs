@@ -0,0 +1,142 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+
+/// Crowns `word` for `account` after `blocks` more blocks.
+fn win_after(contract: &mut Contract, blocks: u64, account: &near_sdk::AccountId, word: &str) {
+    advance_blocks(blocks);
+    let request_id = guess(contract, account, word);
+    judge(contract, request_id, true);
+}
+
+fn changes(contract: &Contract, from_block: u64, to_block: u64, limit: Option<u64>) -> Vec<String> {
+    contract
+        .get_champion_changes(from_block, to_block, limit)
+        .into_iter()
+        .map(|record| format!("{}@{}", record.champion, record.block_height))
+        .collect()
+}
+
+#[test]
+fn champion_changes_within_a_block_range() {
+    let mut contract = new_game();
+    win_after(&mut contract, 10, &alice(), "paper");
+    win_after(&mut contract, 10, &bob(), "scissors");
+    win_after(&mut contract, 10, &carol(), "stone");
+
+    assert_eq!(
+        changes(&contract, START_BLOCK + 10, START_BLOCK + 20, None),
+        vec!["paper@1010", "scissors@1020"]
+    );
+    assert_eq!(
+        changes(&contract, START_BLOCK + 11, START_BLOCK + 30, Some(1)),
+        vec!["scissors@1020"]
+    );
+    assert!(changes(&contract, START_BLOCK + 31, START_BLOCK + 100, None).is_empty());
+}
+
+#[test]
+fn longest_reign_is_kept_across_dethronements() {
+    let mut contract = new_game();
+    assert!(contract.get_longest_reign().is_none());
+
+    win_after(&mut contract, 10, &alice(), "paper");
+    win_after(&mut contract, 30, &bob(), "scissors");
+    win_after(&mut contract, 5, &carol(), "stone");
+
+    let longest = contract.get_longest_reign().unwrap();
+    assert_eq!(longest.champion, "paper");
+    assert_eq!(longest.owner_id, alice());
+    assert_eq!(longest.blocks, 30);
+}
+
+#[test]
+fn average_reign_covers_completed_reigns() {
+    let mut contract = new_game();
+    assert_eq!(contract.get_avg_reign_blocks(), 0);
+
+    win_after(&mut contract, 10, &alice(), "paper");
+    win_after(&mut contract, 30, &bob(), "scissors");
+    win_after(&mut contract, 5, &carol(), "stone");
+    advance_blocks(1_000);
+
+    assert_eq!(contract.get_avg_reign_blocks(), 15);
+}
+
+#[test]
+fn recent_win_count_looks_back_the_given_blocks() {
+    let mut contract = new_game();
+    assert_eq!(contract.get_recent_win_count(1_000), 0);
+
+    win_after(&mut contract, 10, &alice(), "paper");
+    win_after(&mut contract, 30, &bob(), "scissors");
+    win_after(&mut contract, 5, &carol(), "stone");
+
+    assert_eq!(contract.get_recent_win_count(4), 1);
+    assert_eq!(contract.get_recent_win_count(5), 2);
+    assert_eq!(contract.get_recent_win_count(1_000), 3);
+    advance_blocks(10);
+    assert_eq!(contract.get_recent_win_count(9), 0);
+    assert_eq!(contract.get_recent_win_count(10), 1);
+}
+
+#[test]
+fn champion_profile_describes_the_current_reign() {
+    let mut contract = new_game();
+    win_after(&mut contract, 10, &alice(), "paper");
+    let request_id = guess(&mut contract, &bob(), "glue");
+    judge(&mut contract, request_id, false);
+    advance_blocks(25);
+
+    let profile = contract.get_current_champion_profile();
+    assert_eq!(profile.word, "paper");
+    assert_eq!(profile.owner, alice());
+    assert_eq!(profile.since_block, START_BLOCK + 10);
+    assert_eq!(profile.reign_blocks, 25);
+    assert_eq!(profile.reason.as_deref(), Some("because"));
+    assert_eq!(profile.reign_count, 1);
+    assert_eq!(profile.win_rate, Some(0));
+}
+
+#[test]
+fn conquests_follow_a_chain_of_champions() {
+    let mut contract = new_game();
+    win_after(&mut contract, 1, &alice(), "paper");
+    win_after(&mut contract, 1, &bob(), "scissors");
+    win_after(&mut contract, 1, &carol(), "rock");
+    win_after(&mut contract, 1, &alice(), "paper");
+
+    assert_eq!(
+        contract.get_conquests("Paper".to_string(), None, None),
+        vec!["rock", "rock"]
+    );
+    assert_eq!(
+        contract.get_conquests("scissors".to_string(), None, None),
+        vec!["paper"]
+    );
+    assert_eq!(
+        contract.get_conquests("rock".to_string(), None, None),
+        vec!["scissors"]
+    );
+    assert!(contract
+        .get_conquests("stone".to_string(), None, None)
+        .is_empty());
+}
+
+#[test]
+fn warm_up_wins_fill_the_pool_before_the_title_changes() {
+    let mut contract = new_game();
+    call_as(&owner());
+    contract.set_min_champions_to_start(3);
+    assert_eq!(contract.get_min_champions_to_start(), 3);
+
+    win_after(&mut contract, 1, &alice(), "paper");
+    win_after(&mut contract, 1, &bob(), "scissors");
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert_eq!(contract.get_all_champions().len(), 3);
+    assert_eq!(contract.get_player_stats(alice()).unwrap().wins, 1);
+
+    win_after(&mut contract, 1, &carol(), "stone");
+    assert_eq!(contract.get_champion(), "stone");
+}
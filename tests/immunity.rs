@@ -0,0 +1,95 @@
+mod common;
+
+use common::*;
+use near_sdk::NearToken;
+
+fn enable_immunity(contract: &mut contract::Contract) {
+    call_as(&owner());
+    contract.set_immunity_config(NearToken::from_millinear(10), 100);
+}
+
+fn crown_alice(contract: &mut contract::Contract) {
+    let request_id = guess(contract, &alice(), "paper");
+    judge(contract, request_id, true);
+}
+
+#[test]
+fn champion_owner_buys_immunity_blocks() {
+    let mut contract = new_game();
+    enable_immunity(&mut contract);
+    crown_alice(&mut contract);
+
+    call_with(&alice(), NearToken::from_millinear(50));
+    let immune_until = contract.extend_immunity();
+
+    assert_eq!(immune_until, START_BLOCK + 5);
+    assert_eq!(contract.get_immune_until_block(), immune_until);
+}
+
+#[test]
+#[should_panic(expected = "ERR_CHAMPION_IMMUNE")]
+fn immune_champion_rejects_challenges() {
+    let mut contract = new_game();
+    enable_immunity(&mut contract);
+    crown_alice(&mut contract);
+
+    call_with(&alice(), NearToken::from_millinear(50));
+    contract.extend_immunity();
+
+    guess(&mut contract, &bob(), "scissors");
+}
+
+#[test]
+#[should_panic(expected = "ERR_IMMUNITY_CAP")]
+fn immunity_is_capped() {
+    let mut contract = new_game();
+    enable_immunity(&mut contract);
+    crown_alice(&mut contract);
+
+    call_with(&alice(), NearToken::from_millinear(1_010));
+    contract.extend_immunity();
+}
+
+#[test]
+#[should_panic(expected = "Contract in maintenance")]
+fn immunity_cannot_be_bought_during_maintenance() {
+    let mut contract = new_game();
+    enable_immunity(&mut contract);
+    crown_alice(&mut contract);
+
+    call_as(&owner());
+    contract.set_maintenance(true);
+    call_with(&alice(), NearToken::from_millinear(50));
+    contract.extend_immunity();
+}
+
+#[test]
+#[should_panic(expected = "ERR_IMMUNITY_CAP")]
+fn repeat_purchases_cannot_pass_the_reign_cap() {
+    let mut contract = new_game();
+    enable_immunity(&mut contract);
+    crown_alice(&mut contract);
+
+    call_with(&alice(), NearToken::from_millinear(600));
+    assert_eq!(contract.extend_immunity(), START_BLOCK + 60);
+    advance_blocks(50);
+    call_with(&alice(), NearToken::from_millinear(600));
+    contract.extend_immunity();
+}
+
+#[test]
+fn cooldowns_count_down_the_champion_immunity() {
+    let mut contract = new_game();
+    enable_immunity(&mut contract);
+    crown_alice(&mut contract);
+    assert_eq!(contract.get_cooldowns(bob()).champion_immunity_remaining, 0);
+
+    call_with(&alice(), NearToken::from_millinear(50));
+    contract.extend_immunity();
+    assert_eq!(contract.get_cooldowns(bob()).champion_immunity_remaining, 6);
+
+    advance_blocks(5);
+    assert_eq!(contract.get_cooldowns(bob()).champion_immunity_remaining, 1);
+    advance_blocks(1);
+    assert_eq!(contract.get_cooldowns(bob()).champion_immunity_remaining, 0);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::{self, json, Value};
+
+fn records(records: &[(&str, u64)]) -> Value {
+    Value::Array(
+        records
+            .iter()
+            .map(|(champion, block_height)| {
+                json!({
+                    "champion": champion,
+                    "owner_id": "old.near",
+                    "block_height": block_height,
+                })
+            })
+            .collect(),
+    )
+}
+
+fn import(contract: &mut Contract, imported: Value, max: u64) -> u64 {
+    call_as(&owner());
+    contract.import_champions(serde_json::from_value(imported).unwrap(), max)
+}
+
+fn enter_maintenance(contract: &mut Contract) {
+    call_as(&owner());
+    contract.set_maintenance(true);
+}
+
+#[test]
+fn imported_records_predating_the_deployment_are_kept_apart() {
+    let mut contract = new_game();
+    enter_maintenance(&mut contract);
+
+    let imported = import(&mut contract, records(&[("stone", 10), ("Paper", 20)]), 10);
+
+    assert_eq!(imported, 2);
+    let history = contract.get_imported_champions(None, None);
+    let history: Vec<(&str, u64)> = history
+        .iter()
+        .map(|record| (record.champion.as_str(), record.block_height))
+        .collect();
+    assert_eq!(history, vec![("stone", 10), ("paper", 20)]);
+    assert_eq!(contract.get_champion_changes(0, u64::MAX, None).len(), 1);
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert!(contract.get_all_champions().contains(&"paper".to_string()));
+}
+
+#[test]
+fn large_imports_are_paged() {
+    let mut contract = new_game();
+    enter_maintenance(&mut contract);
+
+    let batch = records(&[("stone", 10), ("paper", 20), ("scissors", 30)]);
+    assert_eq!(import(&mut contract, batch, 2), 2);
+    assert_eq!(import(&mut contract, records(&[("scissors", 30)]), 2), 1);
+
+    assert_eq!(contract.get_imported_champions(Some(2), None).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "ERR_RECORD_OUT_OF_ORDER")]
+fn imported_records_must_be_in_block_order() {
+    let mut contract = new_game();
+    enter_maintenance(&mut contract);
+
+    import(&mut contract, records(&[("paper", 20)]), 10);
+    import(&mut contract, records(&[("stone", 10)]), 10);
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_IN_MAINTENANCE")]
+fn imports_need_maintenance() {
+    let mut contract = new_game();
+
+    import(&mut contract, records(&[("stone", 10)]), 10);
+}
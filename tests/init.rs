@@ -0,0 +1,71 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::NearToken;
+
+fn new_game_with(initial_champions: Vec<String>) -> Contract {
+    set_context(&owner(), NearToken::from_yoctonear(0), START_BLOCK);
+    Contract::new(
+        owner(),
+        operator(),
+        INITIAL_CHAMPION.to_string(),
+        None,
+        Some(initial_champions),
+        "agent".to_string(),
+        "Decide whether the guess beats the champion.".to_string(),
+        agent_public_key(),
+        None,
+    )
+}
+
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn initial_champions_seed_the_set() {
+    let contract = new_game_with(words(&["Paper", "scissors", "paper", "rock"]));
+
+    let mut champions = contract.get_all_champions();
+    champions.sort();
+    assert_eq!(champions, words(&["paper", "rock", "scissors"]));
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+}
+
+#[test]
+#[should_panic(expected = "ERR_TOO_MANY_INITIAL_CHAMPIONS")]
+fn initial_champions_are_capped() {
+    new_game_with((0..101).map(|index| format!("word{}", index)).collect());
+}
+
+#[test]
+#[should_panic(expected = "Illegal input string")]
+fn initial_champions_are_validated() {
+    new_game_with(words(&["paper", "not a word"]));
+}
+
+#[test]
+fn initial_champion_is_unowned_by_default() {
+    let contract = new_game();
+
+    assert_eq!(contract.get_champion_owner(), game());
+}
+
+#[test]
+fn initial_champion_owner_can_be_set() {
+    set_context(&owner(), NearToken::from_yoctonear(0), START_BLOCK);
+    let contract = Contract::new(
+        owner(),
+        operator(),
+        INITIAL_CHAMPION.to_string(),
+        Some(owner()),
+        None,
+        "agent".to_string(),
+        "Decide whether the guess beats the champion.".to_string(),
+        agent_public_key(),
+        None,
+    );
+
+    assert_eq!(contract.get_champion_owner(), owner());
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::*;
+
+#[test]
+fn consistent_state_reports_no_issues() {
+    let mut contract = new_game();
+    let resolved = guess(&mut contract, &alice(), "paper");
+    guess(&mut contract, &bob(), "water");
+    judge(&mut contract, resolved, false);
+
+    let report = contract.verify_state_integrity(None, None);
+
+    assert_eq!(report.next_request_id, 2);
+    assert_eq!(report.orphan_responses.count, 0);
+    assert_eq!(report.expired_requests.count, 0);
+    assert_eq!(report.duplicate_data_ids.count, 0);
+}
+
+#[test]
+fn seeded_inconsistencies_are_detected() {
+    let mut contract = new_game();
+    let first = guess(&mut contract, &alice(), "paper");
+    let second = guess(&mut contract, &bob(), "water");
+    let shared = near_sdk::bs58::encode(data_id(&contract, first)).into_string();
+
+    call_as(&owner());
+    contract.repair_request(second, Some(shared));
+    advance_blocks(201);
+    let report = contract.verify_state_integrity(None, None);
+
+    assert_eq!(report.duplicate_data_ids.count, 1);
+    assert_eq!(report.duplicate_data_ids.sample, vec![second]);
+    assert_eq!(report.expired_requests.count, 2);
+    assert_eq!(report.expired_requests.sample, vec![first, second]);
+}
+
+#[test]
+fn report_pages_through_request_ids() {
+    let mut contract = new_game();
+    for word in ["paper", "water", "cloth"] {
+        guess(&mut contract, &alice(), word);
+    }
+    advance_blocks(201);
+
+    let report = contract.verify_state_integrity(Some(1), Some(1));
+    assert_eq!(report.next_request_id, 2);
+    assert_eq!(report.expired_requests.sample, vec![1]);
+
+    let report = contract.verify_state_integrity(Some(report.next_request_id), Some(10));
+    assert_eq!(report.next_request_id, 3);
+    assert_eq!(report.expired_requests.sample, vec![2]);
+}
+
+#[test]
+fn snapshot_hash_is_stable_for_identical_state() {
+    let mut contract = new_game();
+    for word in ["paper", "water", "cloth"] {
+        guess(&mut contract, &alice(), word);
+    }
+    let first_page = contract.get_state_snapshot_hash(None, Some(2));
+    let second_page = contract.get_state_snapshot_hash(Some(2), Some(2));
+    assert_ne!(first_page, second_page);
+
+    advance_blocks(10);
+    assert_eq!(contract.get_state_snapshot_hash(None, Some(2)), first_page);
+
+    call_as(&operator());
+    contract.remove_request(2);
+    assert_eq!(contract.get_state_snapshot_hash(None, Some(2)), first_page);
+    assert_ne!(
+        contract.get_state_snapshot_hash(Some(2), Some(2)),
+        second_page
+    );
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use common::*;
+use contract::{Contract, Response};
+use near_sdk::{bs58, env, serde_json};
+use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+
+fn secp_key() -> SecretKey {
+    SecretKey::from_slice(&[5; 32]).unwrap()
+}
+
+fn secp_public_key(key: &SecretKey) -> String {
+    let public_key = PublicKey::from_secret_key(&Secp256k1::new(), key).serialize_uncompressed();
+    format!("secp256k1:{}", bs58::encode(&public_key[1..]).into_string())
+}
+
+/// `r || s || v` over the keccak256 hash of `message`.
+fn secp_sign(key: &SecretKey, message: &[u8]) -> String {
+    let hash = Message::from_slice(&env::keccak256_array(message)).unwrap();
+    let (recovery_id, signature) = Secp256k1::new()
+        .sign_ecdsa_recoverable(&hash, key)
+        .serialize_compact();
+    let mut bytes = signature.to_vec();
+    bytes.push(recovery_id.to_i32() as u8);
+    bs58::encode(bytes).into_string()
+}
+
+fn register_secp_judge(contract: &mut Contract, public_key: String) {
+    call_as(&owner());
+    contract.register_agent(
+        "secp".to_string(),
+        public_key,
+        Some(serde_json::from_value("Secp256k1".into()).unwrap()),
+        "You are a strict judge.".to_string(),
+    );
+}
+
+fn ask_secp_judge(contract: &mut Contract, message: &str) -> u64 {
+    call_as(&alice());
+    let request_id = contract.get_next_request_id();
+    contract.request(
+        message.to_string(),
+        Some("secp".to_string()),
+        None,
+        None,
+        None,
+    );
+    request_id
+}
+
+fn secp_ruling(key: &SecretKey, contract: &Contract, request_id: u64) -> Response {
+    let challenge = request_json(contract, request_id)["challenge"]
+        .as_str()
+        .map(str::to_string);
+    let data = Some(ruling_data(contract, request_id, true).to_string());
+    let payload = near_sdk::borsh::to_vec(&(request_id, true, &data, &challenge)).unwrap();
+
+    Response {
+        ok: true,
+        signature: Some(secp_sign(key, &payload)),
+        data,
+        challenge,
+        originator: None,
+    }
+}
+
+#[test]
+fn secp256k1_agent_rules_with_its_key() {
+    let mut contract = new_game();
+    register_secp_judge(&mut contract, secp_public_key(&secp_key()));
+    let request_id = ask_secp_judge(&mut contract, "paper");
+
+    let response = secp_ruling(&secp_key(), &contract, request_id);
+    assert!(contract.verify_response(request_id, response.clone()));
+    respond(&mut contract, request_id, response);
+    resolve(&mut contract, request_id);
+
+    assert_eq!(contract.get_champion(), "paper");
+}
+
+#[test]
+fn secp256k1_signature_from_another_key_is_rejected() {
+    let mut contract = new_game();
+    register_secp_judge(&mut contract, secp_public_key(&secp_key()));
+    let request_id = ask_secp_judge(&mut contract, "paper");
+
+    let other_key = SecretKey::from_slice(&[6; 32]).unwrap();
+    let response = secp_ruling(&other_key, &contract, request_id);
+
+    assert!(!contract.verify_response(request_id, response));
+}
+
+#[test]
+fn ed25519_signature_is_rejected_for_a_secp256k1_agent() {
+    let mut contract = new_game();
+    register_secp_judge(&mut contract, secp_public_key(&secp_key()));
+    let request_id = ask_secp_judge(&mut contract, "paper");
+
+    let response = ruling(&contract, request_id, true);
+
+    assert!(!contract.verify_response(request_id, response));
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_PUBLIC_KEY")]
+fn key_must_match_its_type() {
+    let mut contract = new_game();
+
+    register_secp_judge(&mut contract, agent_public_key());
+}
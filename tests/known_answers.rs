@@ -0,0 +1,106 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json;
+use near_sdk::NearToken;
+
+fn set_known_answer(contract: &mut Contract, word: &str, wins: bool) {
+    call_as(&owner());
+    contract.set_known_answer(word.to_string(), wins);
+}
+
+#[test]
+fn known_win_crowns_without_the_agent() {
+    let mut contract = new_game();
+    set_known_answer(&mut contract, "Paper", true);
+
+    guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(contract.get_champion(), "paper");
+    assert_eq!(contract.get_champion_owner(), alice());
+    assert!(contract.get_requests().is_empty());
+}
+
+#[test]
+fn known_loss_keeps_the_deposit() {
+    let mut contract = new_game();
+    set_known_answer(&mut contract, "feather", false);
+
+    guess_with(&mut contract, &alice(), "feather", near(1));
+
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert_eq!(contract.get_prize_pool(), near(1));
+    assert!(contract.get_requests().is_empty());
+}
+
+#[test]
+fn removed_answer_goes_to_the_agent_again() {
+    let mut contract = new_game();
+    set_known_answer(&mut contract, "paper", true);
+    call_as(&owner());
+    contract.remove_known_answer("PAPER".to_string());
+
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert_eq!(request_json(&contract, request_id)["message"], "paper");
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_AN_OWNER")]
+fn only_the_owner_sets_known_answers() {
+    let mut contract = new_game();
+    call_as(&operator());
+    contract.set_known_answer("paper".to_string(), true);
+}
+
+#[test]
+fn known_answers_are_logged_and_counted_like_rulings() {
+    let mut contract = new_game();
+    set_known_answer(&mut contract, "feather", false);
+    set_known_answer(&mut contract, "paper", true);
+
+    let lost = guess(&mut contract, &alice(), "feather");
+    let won = guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(won, lost + 1);
+    assert_eq!(contract.get_next_request_id(), won + 1);
+    let history: Vec<serde_json::Value> = contract
+        .get_account_history(alice(), None, None)
+        .into_iter()
+        .map(|event| serde_json::to_value(event).unwrap())
+        .collect();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0]["outcome"], "Lost");
+    assert_eq!(history[1]["outcome"], "Won");
+    assert_eq!(history[1]["request_id"], won);
+    let log = contract.get_responses_log(None, None);
+    assert_eq!(log[1].reason.as_deref(), Some("known answer"));
+    let counts = contract.get_outcome_counts();
+    assert_eq!((counts.wins, counts.losses), (1, 1));
+    assert_eq!(
+        contract.get_champion_win_rate("rock".to_string()),
+        Some(5_000)
+    );
+    assert_eq!(
+        contract.get_matchup("paper".to_string(), "rock".to_string()),
+        Some(true)
+    );
+}
+
+#[test]
+fn known_win_pays_the_win_reward() {
+    let mut contract = new_game();
+    contract.set_win_reward(NearToken::from_millinear(100), NearToken::from_millinear(1));
+    let request_id = guess_with(&mut contract, &carol(), "feather", near(10));
+    judge(&mut contract, request_id, false);
+    set_known_answer(&mut contract, "paper", true);
+    advance_blocks(10);
+    let reward = contract.get_potential_reward();
+
+    guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(reward, NearToken::from_millinear(110));
+    assert_eq!(transferred_to(&alice()), reward);
+}
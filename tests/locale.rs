@@ -0,0 +1,46 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json;
+
+fn guess_in(contract: &mut Contract, message: &str, locale: &str) -> u64 {
+    call_as(&alice());
+    let request_id = contract.get_next_request_id();
+    contract.request(
+        message.to_string(),
+        None,
+        None,
+        Some(locale.to_string()),
+        None,
+    );
+    request_id
+}
+
+#[test]
+fn locale_is_passed_to_the_agent() {
+    let mut contract = new_game();
+
+    let request_id = guess_in(&mut contract, "Papier", "de-DE");
+
+    let agent_data = serde_json::to_value(contract.agent_data(request_id)).unwrap();
+    assert_eq!(agent_data["locale"], "de-DE");
+    assert_eq!(agent_data["request"]["message"], "papier");
+}
+
+#[test]
+fn requests_without_a_locale_have_none() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    let agent_data = serde_json::to_value(contract.agent_data(request_id)).unwrap();
+    assert!(agent_data["locale"].is_null());
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_LOCALE")]
+fn malformed_locale_is_rejected() {
+    let mut contract = new_game();
+
+    guess_in(&mut contract, "papier", "german_de");
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+
+fn enter_maintenance(contract: &mut Contract) {
+    call_as(&owner());
+    contract.set_maintenance(true);
+}
+
+#[test]
+fn maintenance_is_separate_from_pause() {
+    let mut contract = new_game();
+    assert!(!contract.is_maintenance());
+
+    enter_maintenance(&mut contract);
+
+    assert!(contract.is_maintenance());
+    assert!(!contract.is_paused());
+}
+
+#[test]
+#[should_panic(expected = "Contract in maintenance")]
+fn maintenance_blocks_new_requests() {
+    let mut contract = new_game();
+    enter_maintenance(&mut contract);
+
+    guess(&mut contract, &alice(), "paper");
+}
+
+#[test]
+#[should_panic(expected = "Contract in maintenance")]
+fn maintenance_blocks_cancellations() {
+    let mut contract = new_game();
+    guess(&mut contract, &alice(), "paper");
+    enter_maintenance(&mut contract);
+
+    call_as(&alice());
+    contract.cancel_my_requests(10);
+}
+
+#[test]
+fn open_requests_still_resolve_during_maintenance() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    enter_maintenance(&mut contract);
+
+    judge(&mut contract, request_id, true);
+
+    assert_eq!(contract.get_champion(), "paper");
+    assert!(contract.get_requests().is_empty());
+}
+
+#[test]
+fn leaving_maintenance_accepts_requests_again() {
+    let mut contract = new_game();
+    enter_maintenance(&mut contract);
+    call_as(&owner());
+    contract.set_maintenance(false);
+
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(request_json(&contract, request_id)["message"], "paper");
+}
+
+#[test]
+fn readiness_flips_with_each_condition() {
+    let mut contract = new_game();
+    assert!(contract.is_ready());
+
+    contract.set_paused(true);
+    assert!(!contract.is_ready());
+    contract.set_paused(false);
+    assert!(contract.is_ready());
+
+    contract.set_maintenance(true);
+    assert!(!contract.is_ready());
+    contract.set_maintenance(false);
+    assert!(contract.is_ready());
+
+    contract.set_system_prompt(String::new());
+    assert!(!contract.is_ready());
+    contract.set_system_prompt("Decide.".to_string());
+    assert!(contract.is_ready());
+}
+
+#[test]
+#[should_panic(expected = "Contract in maintenance")]
+fn maintenance_blocks_decay() {
+    let mut contract = new_game();
+    contract.set_decay_blocks(Some(10));
+    let request_id = guess(&mut contract, &alice(), "paper");
+    judge(&mut contract, request_id, true);
+    enter_maintenance(&mut contract);
+
+    advance_blocks(10);
+    assert!(!contract.get_gating_status().decay_pending);
+    call_as(&carol());
+    contract.decay_champion();
+}
+
+#[test]
+#[should_panic(expected = "Contract in maintenance")]
+fn maintenance_blocks_forced_timeouts() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    enter_maintenance(&mut contract);
+
+    advance_blocks(201);
+    call_as(&bob());
+    contract.force_timeout(request_id);
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use common::*;
+
+#[test]
+fn rulings_are_remembered_per_matchup() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "feather");
+    judge(&mut contract, request_id, false);
+    let request_id = guess(&mut contract, &bob(), "paper");
+    judge(&mut contract, request_id, true);
+
+    assert_eq!(
+        contract.get_matchup("feather".to_string(), "rock".to_string()),
+        Some(false)
+    );
+    assert_eq!(
+        contract.get_matchup("Paper".to_string(), "ROCK".to_string()),
+        Some(true)
+    );
+}
+
+#[test]
+fn unknown_matchup_is_none() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    judge(&mut contract, request_id, true);
+
+    assert_eq!(
+        contract.get_matchup("rock".to_string(), "paper".to_string()),
+        None
+    );
+}
+
+#[test]
+fn known_answers_are_remembered_too() {
+    let mut contract = new_game();
+    call_as(&owner());
+    contract.set_known_answer("paper".to_string(), true);
+
+    guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(
+        contract.get_matchup("paper".to_string(), "rock".to_string()),
+        Some(true)
+    );
+}
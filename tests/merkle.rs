@@ -0,0 +1,59 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::{bs58, env, serde_json};
+use std::convert::TryInto;
+
+fn decode(hash: &str) -> [u8; 32] {
+    bs58::decode(hash).into_vec().unwrap().try_into().unwrap()
+}
+
+/// Folds the proof of `word` up to a root, as a light client would.
+fn root_from_proof(contract: &Contract, word: &str) -> String {
+    let proof = serde_json::to_value(contract.get_champion_proof(word.to_string())).unwrap();
+    let mut hash = env::sha256_array(word.as_bytes());
+    for step in proof.as_array().unwrap() {
+        let sibling = decode(step["sibling"].as_str().unwrap());
+        hash = if step["sibling_is_left"].as_bool().unwrap() {
+            env::sha256_array([sibling, hash].concat())
+        } else {
+            env::sha256_array([hash, sibling].concat())
+        };
+    }
+    bs58::encode(hash).into_string()
+}
+
+fn crown_words(contract: &mut Contract, words: &[&str]) {
+    for word in words {
+        let request_id = guess(contract, &alice(), word);
+        judge(contract, request_id, true);
+    }
+}
+
+#[test]
+fn root_only_changes_with_the_champion_set() {
+    let mut contract = new_game();
+    crown_words(&mut contract, &["paper", "scissors"]);
+    let root = contract.get_champions_root();
+    assert_eq!(contract.get_champions_root(), root);
+
+    let request_id = guess(&mut contract, &bob(), "feather");
+    judge(&mut contract, request_id, false);
+    assert_eq!(contract.get_champions_root(), root);
+
+    crown_words(&mut contract, &["stone"]);
+    assert_ne!(contract.get_champions_root(), root);
+}
+
+#[test]
+fn every_champion_proves_inclusion() {
+    let mut contract = new_game();
+    crown_words(&mut contract, &["paper", "scissors", "stone", "hammer"]);
+    let root = contract.get_champions_root();
+
+    for word in ["rock", "paper", "scissors", "stone", "hammer"] {
+        assert_eq!(root_from_proof(&contract, word), root, "{}", word);
+    }
+    assert!(contract.get_champion_proof("feather".to_string()).is_none());
+}
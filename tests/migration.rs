@@ -0,0 +1,166 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, UnorderedMap, UnorderedSet};
+use near_sdk::serde_json::json;
+use near_sdk::{env, AccountId, BorshStorageKey, Gas, GasWeight, NearToken};
+use std::convert::TryInto;
+
+/// State layout of the first deployment, before versioning.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "near_sdk::borsh")]
+struct RequestV0 {
+    data_id: [u8; 32],
+    originator_id: AccountId,
+    message: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "near_sdk::borsh")]
+struct ResponseV0 {
+    ok: bool,
+    data: Option<String>,
+    signature: Option<String>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "near_sdk::borsh")]
+struct ContractV0 {
+    agent_name: String,
+    agent_public_key: String,
+    agent_system_prompt: String,
+    paused: bool,
+    requests: UnorderedMap<u64, RequestV0>,
+    responses: LookupMap<u64, ResponseV0>,
+    num_requests: u64,
+    owner_id: AccountId,
+    operator_id: AccountId,
+    current_champion: String,
+    champion_owner: AccountId,
+    all_champions: UnorderedSet<String>,
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+#[borsh(crate = "near_sdk::borsh")]
+enum StorageKey {
+    Requests,
+    Responses,
+    AllChampions,
+}
+
+fn new_data_id() -> [u8; 32] {
+    env::promise_yield_create("await_response", [], Gas::from_tgas(1), GasWeight(0), 0);
+    env::read_register(0).unwrap().try_into().unwrap()
+}
+
+/// Writes a first deployment state with two champions and `guesses` open,
+/// the first already answered by a ruling that it wins.
+fn deploy_v0(guesses: &[(&AccountId, &str)]) {
+    set_context(&owner(), NearToken::from_yoctonear(0), START_BLOCK);
+    let mut all_champions = UnorderedSet::new(StorageKey::AllChampions);
+    all_champions.insert(&"stone".to_string());
+    all_champions.insert(&INITIAL_CHAMPION.to_string());
+    let mut requests = UnorderedMap::new(StorageKey::Requests);
+    let mut responses = LookupMap::new(StorageKey::Responses);
+
+    for (request_id, (account, message)) in guesses.iter().enumerate() {
+        let request_id = request_id as u64 + 1;
+        requests.insert(
+            &request_id,
+            &RequestV0 {
+                data_id: new_data_id(),
+                originator_id: (*account).clone(),
+                message: message.to_string(),
+            },
+        );
+        if request_id == 1 {
+            let ruling = json!({
+                "current_champion": INITIAL_CHAMPION,
+                "guess_wins": true,
+                "reason": "because",
+            });
+            responses.insert(
+                &request_id,
+                &ResponseV0 {
+                    ok: true,
+                    data: Some(ruling.to_string()),
+                    signature: Some("signature".to_string()),
+                },
+            );
+        }
+    }
+
+    env::state_write(&ContractV0 {
+        agent_name: "agent".to_string(),
+        agent_public_key: agent_public_key(),
+        agent_system_prompt: "Decide whether the guess beats the champion.".to_string(),
+        paused: false,
+        requests,
+        responses,
+        num_requests: guesses.len() as u64 + 1,
+        owner_id: owner(),
+        operator_id: operator(),
+        current_champion: INITIAL_CHAMPION.to_string(),
+        champion_owner: carol(),
+        all_champions,
+    });
+}
+
+fn migrate() -> Contract {
+    call_as(&game());
+    Contract::migrate()
+}
+
+#[test]
+fn migration_keeps_the_game_state() {
+    deploy_v0(&[(&alice(), "paper")]);
+    let contract = migrate();
+
+    assert_eq!(contract.get_state_version(), 1);
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert_eq!(contract.get_champion_owner(), carol());
+    assert_eq!(contract.get_all_champions().len(), 2);
+    assert_eq!(contract.get_next_request_id(), 2);
+}
+
+#[test]
+fn migrated_requests_get_a_challenge_and_can_be_answered() {
+    deploy_v0(&[(&alice(), "paper"), (&bob(), "water")]);
+    let mut contract = migrate();
+
+    let request = request_json(&contract, 2);
+    assert_eq!(request["originator_id"], "bob.near");
+    assert_eq!(request["champion_at_request"], INITIAL_CHAMPION);
+    assert!(!request["challenge"].as_str().unwrap().is_empty());
+
+    judge(&mut contract, 2, true);
+    assert_eq!(contract.get_champion(), "water");
+}
+
+#[test]
+fn migrated_requests_have_no_tags() {
+    deploy_v0(&[(&alice(), "paper")]);
+    let contract = migrate();
+
+    assert_eq!(request_json(&contract, 1)["tags"], json!([]));
+}
+
+#[test]
+fn submitted_ruling_resolves_after_migration() {
+    deploy_v0(&[(&alice(), "paper")]);
+    let mut contract = migrate();
+
+    resolve(&mut contract, 1);
+
+    assert_eq!(contract.get_champion(), "paper");
+    assert_eq!(contract.get_champion_owner(), alice());
+}
+
+#[test]
+#[should_panic(expected = "ERR_ALREADY_MIGRATED")]
+fn migration_runs_once() {
+    new_game();
+    migrate();
+}
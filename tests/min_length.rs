@@ -0,0 +1,32 @@
+mod common;
+
+use common::*;
+
+#[test]
+fn single_letters_are_accepted_by_default() {
+    let mut contract = new_game();
+    assert_eq!(contract.get_min_champion_len(), 1);
+
+    guess(&mut contract, &alice(), "a");
+
+    assert_eq!(contract.get_requests().len(), 1);
+}
+
+#[test]
+fn guess_at_the_minimum_length_is_accepted() {
+    let mut contract = new_game();
+    contract.set_min_champion_len(3);
+
+    guess(&mut contract, &alice(), "axe");
+
+    assert_eq!(contract.get_requests().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "ERR_GUESS_TOO_SHORT")]
+fn guess_below_the_minimum_length_is_rejected() {
+    let mut contract = new_game();
+    contract.set_min_champion_len(3);
+
+    guess(&mut contract, &alice(), "ox");
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+
+fn open_three_requests(contract: &mut Contract) -> Vec<u64> {
+    for (account, word) in [(alice(), "paper"), (bob(), "water"), (carol(), "cloth")] {
+        guess(contract, &account, word);
+    }
+    contract.get_requests().iter().map(|(id, _)| *id).collect()
+}
+
+#[test]
+fn open_request_ids_match_get_requests() {
+    let mut contract = new_game();
+    let first = guess(&mut contract, &alice(), "paper");
+    open_three_requests(&mut contract);
+    judge(&mut contract, first, false);
+
+    let keys: Vec<u64> = contract.get_requests().iter().map(|(id, _)| *id).collect();
+
+    assert_eq!(contract.get_open_request_ids(None, None), keys);
+    assert_eq!(keys.len(), 3);
+}
+
+#[test]
+fn open_request_ids_are_paged() {
+    let mut contract = new_game();
+    let keys = open_three_requests(&mut contract);
+
+    assert_eq!(contract.get_open_request_ids(Some(1), Some(1)), keys[1..2]);
+    assert!(contract.get_open_request_ids(Some(3), None).is_empty());
+}
+
+#[test]
+fn pending_requests_are_grouped_by_originator() {
+    let mut contract = new_game();
+    let mut ids = vec![];
+    for (account, word) in [
+        (bob(), "paper"),
+        (alice(), "water"),
+        (bob(), "cloth"),
+        (carol(), "stone"),
+        (bob(), "scissors"),
+        (carol(), "glue"),
+    ] {
+        ids.push(guess(&mut contract, &account, word));
+    }
+    judge(&mut contract, ids[5], false);
+
+    let mut pending = contract.get_pending_by_originator(None, None);
+    for (_, request_ids) in pending.iter_mut() {
+        request_ids.sort();
+    }
+    assert_eq!(
+        pending,
+        vec![
+            (bob(), vec![ids[0], ids[2], ids[4]]),
+            (alice(), vec![ids[1]]),
+            (carol(), vec![ids[3]]),
+        ]
+    );
+    assert_eq!(
+        contract.get_pending_by_originator(Some(1), Some(1))[0].0,
+        alice()
+    );
+}
+
+#[test]
+fn next_request_id_predicts_the_assigned_id() {
+    let mut contract = new_game();
+    assert_eq!(contract.get_next_request_id(), 0);
+
+    let first = contract.get_next_request_id();
+    guess(&mut contract, &alice(), "paper");
+    assert_eq!(contract.get_requests()[0].0, first);
+    judge(&mut contract, first, false);
+
+    assert_eq!(contract.get_next_request_id(), 1);
+    guess(&mut contract, &bob(), "water");
+    assert_eq!(contract.get_next_request_id(), 2);
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use common::*;
+
+/// Distinct lowercase word for `index`.
+fn word(index: usize) -> String {
+    let letters = [index / 26 % 26, index % 26];
+    letters
+        .iter()
+        .map(|letter| (b'a' + *letter as u8) as char)
+        .collect::<String>()
+        + "word"
+}
+
+#[test]
+fn paged_views_clamp_the_limit() {
+    let mut contract = new_game();
+    for index in 0..210 {
+        let account = [alice(), bob(), carol()][index % 3].clone();
+        guess(&mut contract, &account, &word(index));
+    }
+
+    assert_eq!(contract.get_open_request_ids(None, None).len(), 50);
+    assert_eq!(contract.get_open_request_ids(None, Some(1_000)).len(), 200);
+    assert_eq!(
+        contract.get_open_request_ids(Some(5), Some(u64::MAX)).len(),
+        200
+    );
+    assert_eq!(
+        contract.get_open_request_ids(Some(200), Some(1_000)).len(),
+        10
+    );
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json;
+
+fn system_prompt(contract: &Contract) -> String {
+    let (_, value) = contract
+        .get_config_kv()
+        .into_iter()
+        .find(|(key, _)| key == "system_prompt")
+        .unwrap();
+    serde_json::from_str(&value).unwrap()
+}
+
+/// Operator prompt changes must be ten blocks apart.
+fn game_with_prompt_cooldown() -> Contract {
+    let mut contract = new_game();
+    contract.set_min_blocks_between_prompt_changes(10);
+    contract
+}
+
+#[test]
+#[should_panic(expected = "ERR_PROMPT_CHANGE_TOO_SOON")]
+fn operator_cannot_change_the_prompt_within_the_window() {
+    let mut contract = game_with_prompt_cooldown();
+
+    advance_blocks(9);
+    call_as(&operator());
+    contract.set_system_prompt("Be strict.".to_string());
+}
+
+#[test]
+fn operator_changes_the_prompt_once_the_window_has_passed() {
+    let mut contract = game_with_prompt_cooldown();
+
+    advance_blocks(10);
+    call_as(&operator());
+    contract.set_system_prompt("Be strict.".to_string());
+
+    assert_eq!(system_prompt(&contract), "Be strict.");
+}
+
+#[test]
+fn owner_overrides_the_window() {
+    let mut contract = game_with_prompt_cooldown();
+
+    call_as(&owner());
+    contract.set_system_prompt("Be strict.".to_string());
+    contract.set_system_prompt("Be lenient.".to_string());
+
+    assert_eq!(system_prompt(&contract), "Be lenient.");
+}
+
+#[test]
+#[should_panic(expected = "ERR_PROMPT_CHANGE_TOO_SOON")]
+fn owner_change_restarts_the_window() {
+    let mut contract = game_with_prompt_cooldown();
+    advance_blocks(10);
+    call_as(&owner());
+    contract.set_system_prompt("Be strict.".to_string());
+
+    advance_blocks(5);
+    call_as(&operator());
+    contract.set_system_prompt("Be lenient.".to_string());
+}
+
+#[test]
+fn cooldowns_count_down_to_the_next_prompt_change() {
+    let mut contract = game_with_prompt_cooldown();
+
+    advance_blocks(4);
+    assert_eq!(contract.get_cooldowns(operator()).prompt_change, 6);
+    assert_eq!(contract.get_cooldowns(owner()).prompt_change, 0);
+
+    advance_blocks(6);
+    assert_eq!(contract.get_cooldowns(operator()).prompt_change, 0);
+    call_as(&operator());
+    contract.set_system_prompt("Be strict.".to_string());
+
+    advance_blocks(3);
+    assert_eq!(contract.get_cooldowns(operator()).prompt_change, 7);
+}
+
+fn request_prompt(contract: &Contract, request_id: u64) -> String {
+    let agent_data = serde_json::to_value(contract.agent_data(request_id)).unwrap();
+    agent_data["prompt"].as_str().unwrap().to_string()
+}
+
+const DEFAULT_PROMPT: &str = "Decide whether the guess beats the champion.";
+
+#[test]
+fn champion_prompt_applies_once_its_word_is_crowned() {
+    let mut contract = new_game();
+    contract.set_champion_prompt("Paper".to_string(), Some("Paper is thin.".to_string()));
+    let request_id = guess(&mut contract, &alice(), "paper");
+    assert_eq!(request_prompt(&contract, request_id), DEFAULT_PROMPT);
+
+    judge(&mut contract, request_id, true);
+    let request_id = guess(&mut contract, &bob(), "scissors");
+
+    assert_eq!(request_prompt(&contract, request_id), "Paper is thin.");
+}
+
+#[test]
+fn removed_champion_prompt_falls_back_to_the_default() {
+    let mut contract = new_game();
+    contract.set_champion_prompt("rock".to_string(), Some("Rock is hard.".to_string()));
+    let request_id = guess(&mut contract, &alice(), "paper");
+    assert_eq!(request_prompt(&contract, request_id), "Rock is hard.");
+
+    call_as(&owner());
+    contract.set_champion_prompt("rock".to_string(), None);
+
+    assert!(contract.get_champion_prompt("rock".to_string()).is_none());
+    assert_eq!(request_prompt(&contract, request_id), DEFAULT_PROMPT);
+}
+
+fn set_segment(contract: &mut Contract, segment: &str, text: &str) {
+    call_as(&owner());
+    contract.set_prompt_segment(
+        serde_json::from_value(segment.into()).unwrap(),
+        Some(text.to_string()),
+    );
+}
+
+#[test]
+fn segments_are_assembled_in_a_fixed_order() {
+    let mut contract = new_game();
+    assert!(contract.get_assembled_prompt().is_none());
+
+    set_segment(&mut contract, "Constraints", "Answer in JSON.");
+    set_segment(&mut contract, "Instructions", "{question}");
+    set_segment(&mut contract, "Persona", "You guard {champion}.");
+
+    assert_eq!(
+        contract.get_assembled_prompt().unwrap(),
+        "What beats rock?\n\nYou guard rock.\n\nAnswer in JSON."
+    );
+    let request_id = guess(&mut contract, &alice(), "paper");
+    assert_eq!(
+        request_prompt(&contract, request_id),
+        contract.get_assembled_prompt().unwrap()
+    );
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use ed25519_dalek::SigningKey;
+use near_sdk::serde_json::{self, json, Value};
+
+fn player_key() -> SigningKey {
+    SigningKey::from_bytes(&[9; 32])
+}
+
+fn provenance(message: &str, nonce: u64, key: &SigningKey) -> Value {
+    let payload = near_sdk::borsh::to_vec(&(message, nonce)).unwrap();
+    json!({
+        "public_key": public_key_of(&player_key()),
+        "nonce": nonce,
+        "signature": sign_with(key, &payload),
+    })
+}
+
+fn signed_guess(contract: &mut Contract, message: &str, provenance: Value) -> u64 {
+    call_as(&alice());
+    let request_id = contract.get_next_request_id();
+    contract.request(
+        message.to_string(),
+        None,
+        Some(serde_json::from_value(provenance).unwrap()),
+        None,
+        None,
+    );
+    request_id
+}
+
+#[test]
+fn signed_guess_keeps_its_provenance() {
+    let mut contract = new_game();
+    let signature = provenance("paper", 0, &player_key());
+
+    let request_id = signed_guess(&mut contract, "Paper", signature.clone());
+
+    assert_eq!(request_json(&contract, request_id)["provenance"], signature);
+}
+
+#[test]
+fn provenance_is_optional() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(
+        request_json(&contract, request_id)["provenance"],
+        Value::Null
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_PROVENANCE")]
+fn signature_by_another_key_is_rejected() {
+    let mut contract = new_game();
+    let signature = provenance("paper", 0, &agent_key());
+
+    signed_guess(&mut contract, "paper", signature);
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_PROVENANCE")]
+fn signature_over_another_guess_is_rejected() {
+    let mut contract = new_game();
+    let signature = provenance("scissors", 0, &player_key());
+
+    signed_guess(&mut contract, "paper", signature);
+}
+
+#[test]
+#[should_panic(expected = "ERR_PROVENANCE_TOO_LONG")]
+fn oversized_provenance_is_rejected() {
+    let mut contract = new_game();
+    let mut signature = provenance("paper", 0, &player_key());
+    signature["signature"] = "1".repeat(1_000).into();
+
+    signed_guess(&mut contract, "paper", signature);
+}
+
+#[test]
+fn nonce_advances_with_each_signed_guess() {
+    let mut contract = new_game();
+    assert_eq!(contract.get_nonce(alice()), 0);
+
+    signed_guess(
+        &mut contract,
+        "paper",
+        provenance("paper", 0, &player_key()),
+    );
+    assert_eq!(contract.get_nonce(alice()), 1);
+    assert_eq!(contract.get_nonce(bob()), 0);
+
+    signed_guess(
+        &mut contract,
+        "water",
+        provenance("water", 1, &player_key()),
+    );
+    assert_eq!(contract.get_nonce(alice()), 2);
+}
+
+#[test]
+fn unsigned_guess_leaves_the_nonce() {
+    let mut contract = new_game();
+
+    guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(contract.get_nonce(alice()), 0);
+}
+
+#[test]
+#[should_panic(expected = "ERR_BAD_NONCE")]
+fn replayed_nonce_is_rejected() {
+    let mut contract = new_game();
+    signed_guess(
+        &mut contract,
+        "paper",
+        provenance("paper", 0, &player_key()),
+    );
+
+    signed_guess(
+        &mut contract,
+        "paper",
+        provenance("paper", 0, &player_key()),
+    );
+}
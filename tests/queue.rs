@@ -0,0 +1,112 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json;
+
+/// Caps open requests at `max_open_requests`, queueing the rest.
+fn game_with_queue(max_open_requests: u64) -> Contract {
+    let mut contract = new_game();
+    contract.set_max_open_requests(Some(max_open_requests));
+    let mut flags = serde_json::to_value(contract.get_feature_flags()).unwrap();
+    flags["queue_when_overloaded"] = true.into();
+    contract.set_feature_flags(serde_json::from_value(flags).unwrap());
+    contract
+}
+
+fn open_messages(contract: &Contract) -> Vec<String> {
+    let mut requests = contract.get_requests();
+    requests.sort_by_key(|(request_id, _)| *request_id);
+    requests
+        .into_iter()
+        .map(|(request_id, _)| {
+            request_json(contract, request_id)["message"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect()
+}
+
+#[test]
+#[should_panic(expected = "ERR_TOO_MANY_OPEN_REQUESTS")]
+fn overload_is_rejected_without_the_queue() {
+    let mut contract = new_game();
+    contract.set_max_open_requests(Some(1));
+    guess(&mut contract, &alice(), "paper");
+
+    guess(&mut contract, &bob(), "water");
+}
+
+#[test]
+fn overload_is_queued_without_a_yield() {
+    let mut contract = game_with_queue(1);
+    guess(&mut contract, &alice(), "paper");
+    let next_request_id = contract.get_next_request_id();
+
+    guess(&mut contract, &bob(), "water");
+
+    assert_eq!(contract.get_queue_length(), 1);
+    assert_eq!(contract.get_next_request_id(), next_request_id);
+    assert!(events("run_agent").is_empty());
+    assert_eq!(open_messages(&contract), vec!["paper"]);
+}
+
+#[test]
+fn promotion_is_oldest_first_while_capacity_allows() {
+    let mut contract = game_with_queue(2);
+    let first = guess(&mut contract, &alice(), "paper");
+    let second = guess(&mut contract, &alice(), "water");
+    for word in ["cloth", "scissors", "stone"] {
+        guess(&mut contract, &bob(), word);
+    }
+    judge(&mut contract, first, false);
+    judge(&mut contract, second, false);
+
+    call_as(&operator());
+    assert_eq!(contract.promote_queued(1), 1);
+    assert_eq!(open_messages(&contract), vec!["cloth"]);
+
+    call_as(&operator());
+    assert_eq!(contract.promote_queued(10), 1);
+    assert_eq!(open_messages(&contract), vec!["cloth", "scissors"]);
+    assert_eq!(contract.get_queue_length(), 1);
+}
+
+#[test]
+#[should_panic(expected = "ERR_QUEUE_FULL")]
+fn queue_is_capped_at_max_queue_length() {
+    let mut contract = game_with_queue(1);
+    assert_eq!(contract.get_max_queue_length(), 100);
+    contract.set_max_queue_length(2);
+    guess(&mut contract, &alice(), "paper");
+    guess(&mut contract, &bob(), "water");
+    guess(&mut contract, &bob(), "cloth");
+    assert_eq!(contract.get_queue_length(), 2);
+
+    guess(&mut contract, &carol(), "scissors");
+}
+
+#[test]
+fn promoting_frees_queue_slots() {
+    let mut contract = game_with_queue(1);
+    contract.set_max_queue_length(1);
+    let request_id = guess(&mut contract, &alice(), "paper");
+    guess(&mut contract, &bob(), "water");
+    judge(&mut contract, request_id, false);
+
+    call_as(&operator());
+    assert_eq!(contract.promote_queued(1), 1);
+    guess(&mut contract, &carol(), "scissors");
+
+    assert_eq!(contract.get_queue_length(), 1);
+}
+
+#[test]
+fn queued_requests_count_for_their_agent() {
+    let mut contract = game_with_queue(1);
+    guess(&mut contract, &alice(), "paper");
+    guess(&mut contract, &bob(), "water");
+
+    assert_eq!(contract.get_agent_request_count("agent".to_string()), 2);
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json;
+
+/// Remembers the last two guesses per account and rejects repeats.
+fn game_rejecting_repeats() -> Contract {
+    let mut contract = new_game();
+    contract.set_repeat_guess_window(2);
+    let mut flags = serde_json::to_value(contract.get_feature_flags()).unwrap();
+    flags["reject_repeat_guesses"] = true.into();
+    contract.set_feature_flags(serde_json::from_value(flags).unwrap());
+    contract
+}
+
+#[test]
+#[should_panic(expected = "ERR_REPEATED_GUESS")]
+fn repeated_guess_is_rejected() {
+    let mut contract = game_rejecting_repeats();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    judge(&mut contract, request_id, false);
+
+    guess(&mut contract, &alice(), "PAPER");
+}
+
+#[test]
+fn other_accounts_may_repeat_the_guess() {
+    let mut contract = game_rejecting_repeats();
+    guess(&mut contract, &alice(), "paper");
+
+    guess(&mut contract, &bob(), "paper");
+
+    assert_eq!(contract.get_requests().len(), 2);
+}
+
+#[test]
+fn oldest_guess_is_evicted_from_the_window() {
+    let mut contract = game_rejecting_repeats();
+    for word in ["paper", "water", "cloth"] {
+        guess(&mut contract, &alice(), word);
+    }
+    assert_eq!(contract.get_recent_guesses(alice()), vec!["water", "cloth"]);
+
+    guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(contract.get_recent_guesses(alice()), vec!["cloth", "paper"]);
+}
+
+#[test]
+#[should_panic(expected = "ERR_REPEAT_GUESS_WINDOW_TOO_LARGE")]
+fn window_is_capped() {
+    let mut contract = new_game();
+
+    contract.set_repeat_guess_window(21);
+}
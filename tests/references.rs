@@ -0,0 +1,83 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::{json, Value};
+
+fn answer(contract: &mut Contract, request_id: u64, data: Value) {
+    let response = signed_response(contract, request_id, data.to_string());
+    respond(contract, request_id, response);
+    resolve(contract, request_id);
+}
+
+fn logged_reason(contract: &Contract) -> Option<String> {
+    contract.get_responses_log(None, None).pop().unwrap().reason
+}
+
+#[test]
+fn inline_reason_is_stored_as_is() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    answer(
+        &mut contract,
+        request_id,
+        json!({
+            "current_champion": "rock",
+            "guess_wins": true,
+            "reason": "Paper covers rock.",
+        }),
+    );
+
+    assert_eq!(contract.get_champion(), "paper");
+    assert_eq!(
+        logged_reason(&contract).as_deref(),
+        Some("Paper covers rock.")
+    );
+}
+
+#[test]
+fn referenced_reason_keeps_the_inline_ruling() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    answer(
+        &mut contract,
+        request_id,
+        json!({
+            "current_champion": "rock",
+            "guess_wins": true,
+            "reason": "ipfs://bafybeigdyrzt/ruling.json",
+            "data_is_reference": true,
+        }),
+    );
+
+    assert_eq!(contract.get_champion(), "paper");
+    assert_eq!(
+        logged_reason(&contract).as_deref(),
+        Some("ipfs://bafybeigdyrzt/ruling.json")
+    );
+}
+
+#[test]
+fn malformed_reference_is_not_applied() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    answer(
+        &mut contract,
+        request_id,
+        json!({
+            "current_champion": "rock",
+            "guess_wins": true,
+            "reason": "Paper covers rock.",
+            "data_is_reference": true,
+        }),
+    );
+
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert_eq!(
+        contract.get_request_error(request_id).as_deref(),
+        Some("Illegal response data reference")
+    );
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::*;
+use near_sdk::{env, Gas, GasWeight};
+use std::convert::TryInto;
+
+/// Id of a yield other than the first one created in a context, so it never
+/// matches a request's original id.
+fn fresh_data_id() -> [u8; 32] {
+    for _ in 0..3 {
+        env::promise_yield_create("await_response", [], Gas::from_tgas(1), GasWeight(0), 0);
+    }
+    env::read_register(0).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn repaired_request_resumes_the_new_data_id() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let original = data_id(&contract, request_id);
+
+    call_as(&owner());
+    let repaired = fresh_data_id();
+    contract.repair_request(
+        request_id,
+        Some(near_sdk::bs58::encode(repaired).into_string()),
+    );
+
+    assert_ne!(original, repaired);
+    assert_eq!(data_id(&contract, request_id), repaired);
+    judge(&mut contract, request_id, true);
+    assert_eq!(contract.get_champion(), "paper");
+}
+
+#[test]
+#[should_panic(expected = "ERR_DATA_ID_MISMATCH")]
+fn respond_rejects_a_data_id_other_than_the_stored_one() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let original = data_id(&contract, request_id);
+
+    call_as(&owner());
+    let repaired = fresh_data_id();
+    contract.repair_request(
+        request_id,
+        Some(near_sdk::bs58::encode(repaired).into_string()),
+    );
+
+    let response = ruling(&contract, request_id, true);
+    call_as(&operator());
+    revive_yield(original);
+    contract.respond(original, request_id, response);
+}
+
+#[test]
+fn repair_without_data_id_cancels_the_request() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    call_as(&owner());
+    contract.repair_request(request_id, None);
+
+    assert!(contract.get_requests().is_empty());
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_AN_OWNER")]
+fn only_the_owner_repairs_requests() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    call_as(&operator());
+    contract.repair_request(request_id, None);
+}
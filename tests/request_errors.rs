@@ -0,0 +1,58 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::json;
+
+fn answer(contract: &mut Contract, request_id: u64, data: String) {
+    let response = signed_response(contract, request_id, data);
+    respond(contract, request_id, response);
+    resolve(contract, request_id);
+}
+
+#[test]
+fn malformed_ruling_stores_the_error() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    answer(
+        &mut contract,
+        request_id,
+        "{\"guess_wins\": tru".to_string(),
+    );
+
+    assert_eq!(
+        contract.get_request_error(request_id).as_deref(),
+        Some("Wrong response message format")
+    );
+    assert_eq!(contract.get_champion(), INITIAL_CHAMPION);
+    assert!(contract.get_requests().is_empty());
+}
+
+#[test]
+fn ruling_about_another_champion_stores_the_error() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let data = json!({
+        "current_champion": "scissors",
+        "guess_wins": true,
+        "reason": "because",
+    });
+
+    answer(&mut contract, request_id, data.to_string());
+
+    assert_eq!(
+        contract.get_request_error(request_id).as_deref(),
+        Some("Illegal current champion")
+    );
+}
+
+#[test]
+fn applied_ruling_has_no_error() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    judge(&mut contract, request_id, false);
+
+    assert!(contract.get_request_error(request_id).is_none());
+}
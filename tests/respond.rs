@@ -0,0 +1,136 @@
+mod common;
+
+use common::*;
+
+#[test]
+#[should_panic(expected = "ERR_BAD_REQUEST_ID")]
+fn respond_rejects_unassigned_request_id() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let response = ruling(&contract, request_id, true);
+
+    call_as(&operator());
+    contract.respond([0; 32], request_id + 1, response);
+}
+
+#[test]
+#[should_panic(expected = "ERR_BAD_REQUEST_ID")]
+fn remove_request_rejects_unassigned_request_id() {
+    let mut contract = new_game();
+
+    call_as(&operator());
+    contract.remove_request(0);
+}
+
+#[test]
+fn winning_ruling_crowns_the_guess() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    judge(&mut contract, request_id, true);
+
+    assert_eq!(contract.get_champion(), "paper");
+    assert_eq!(contract.get_champion_owner(), alice());
+    assert!(contract.get_requests().is_empty());
+}
+
+#[test]
+fn verify_response_accepts_the_agent_signature() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    assert!(contract.verify_response(request_id, ruling(&contract, request_id, true)));
+    assert_eq!(contract.get_requests().len(), 1);
+}
+
+#[test]
+fn verify_response_rejects_invalid_signatures() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    let mut tampered = ruling(&contract, request_id, false);
+    tampered.data = Some(ruling_data(&contract, request_id, true).to_string());
+    assert!(!contract.verify_response(request_id, tampered));
+
+    let other_key = ed25519_dalek::SigningKey::from_bytes(&[9; 32]);
+    let data = ruling_data(&contract, request_id, true).to_string();
+    let foreign = response_signed_by(&other_key, &contract, request_id, data);
+    assert!(!contract.verify_response(request_id, foreign));
+}
+
+#[test]
+fn verify_response_rejects_unknown_requests() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let response = ruling(&contract, request_id, true);
+
+    assert!(!contract.verify_response(request_id + 1, response));
+}
+
+fn game_requiring_originator() -> contract::Contract {
+    let mut contract = new_game();
+    let mut flags = near_sdk::serde_json::to_value(contract.get_feature_flags()).unwrap();
+    flags["require_matching_originator"] = true.into();
+    contract.set_feature_flags(near_sdk::serde_json::from_value(flags).unwrap());
+    contract
+}
+
+#[test]
+#[should_panic(expected = "ERR_ORIGINATOR_MISMATCH")]
+fn response_for_another_originator_is_rejected() {
+    let mut contract = game_requiring_originator();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let mut response = ruling(&contract, request_id, true);
+    response.originator = Some(bob());
+
+    respond(&mut contract, request_id, response);
+}
+
+#[test]
+fn response_naming_the_originator_is_accepted() {
+    let mut contract = game_requiring_originator();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let mut response = ruling(&contract, request_id, true);
+    assert!(!contract.verify_response(request_id, response.clone()));
+    response.originator = Some(alice());
+
+    respond(&mut contract, request_id, response);
+    resolve(&mut contract, request_id);
+
+    assert_eq!(contract.get_champion(), "paper");
+}
+
+#[test]
+fn payload_bytes_sign_a_response_respond_accepts() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let data = Some(ruling_data(&contract, request_id, true).to_string());
+
+    let payload = contract.agent_payload_bytes(request_id, true, data.clone());
+    let response = contract::Response {
+        ok: true,
+        data,
+        signature: Some(sign(&payload)),
+        challenge: request_json(&contract, request_id)["challenge"]
+            .as_str()
+            .map(str::to_string),
+        originator: None,
+    };
+    assert!(contract.verify_response(request_id, response.clone()));
+    respond(&mut contract, request_id, response);
+    resolve(&mut contract, request_id);
+
+    assert_eq!(contract.get_champion(), "paper");
+}
+
+#[test]
+fn payload_bytes_change_with_the_ruling() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let data = Some(ruling_data(&contract, request_id, true).to_string());
+
+    assert_ne!(
+        contract.agent_payload_bytes(request_id, true, data.clone()),
+        contract.agent_payload_bytes(request_id, false, data)
+    );
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::{self, json};
+use near_sdk::NearToken;
+
+fn start_second_season(contract: &mut Contract) {
+    call_as(&owner());
+    contract.start_new_season(
+        serde_json::from_value(json!({
+            "agent_name": "agent2",
+            "agent_public_key": agent_public_key(),
+            "agent_system_prompt": "Judge the second season.",
+            "question_template": "What defeats {champion}?",
+            "initial_champion": "Water",
+        }))
+        .unwrap(),
+    );
+}
+
+#[test]
+fn new_season_cancels_open_requests_and_reconfigures() {
+    let mut contract = new_game();
+    let crowned = guess(&mut contract, &alice(), "paper");
+    judge(&mut contract, crowned, true);
+    let open = guess_with(&mut contract, &bob(), "scissors", near(1));
+    assert_eq!(contract.get_season(), 0);
+
+    start_second_season(&mut contract);
+
+    assert_eq!(contract.get_season(), 1);
+    assert!(contract.get_requests().is_empty());
+    assert_eq!(transferred_to(&bob()), near(1));
+    assert_eq!(contract.get_escrow(open), NearToken::from_yoctonear(0));
+    assert_eq!(contract.get_champion(), "water");
+    assert_eq!(contract.get_champion_owner(), game());
+    assert_eq!(contract.get_question(), "What defeats water?");
+    assert!(contract.get_all_champions().contains(&"paper".to_string()));
+    let season_started = event("season_started");
+    assert_eq!(season_started["data"][0]["season"], 1);
+    assert_eq!(season_started["data"][0]["champion"], "water");
+    assert_eq!(season_started["data"][0]["agent"], "agent2");
+    assert_eq!(season_started["data"][0]["cancelled_requests"], 1);
+
+    let request_id = guess(&mut contract, &carol(), "sponge");
+    let agent_data = serde_json::to_value(contract.agent_data(request_id)).unwrap();
+    assert_eq!(event("run_agent")["data"][0]["agent"], "agent2");
+    assert_eq!(agent_data["prompt"], "Judge the second season.");
+    judge(&mut contract, request_id, true);
+    assert_eq!(contract.get_champion(), "sponge");
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_AN_OWNER")]
+fn only_the_owner_starts_a_season() {
+    let mut contract = new_game();
+    call_as(&operator());
+    contract.start_new_season(
+        serde_json::from_value(json!({
+            "agent_name": "agent2",
+            "agent_public_key": agent_public_key(),
+            "agent_system_prompt": "Judge the second season.",
+            "initial_champion": "water",
+        }))
+        .unwrap(),
+    );
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::{self, json, Value};
+
+fn seed(contract: &mut Contract, seed: Value) {
+    call_as(&owner());
+    contract.seed_game(serde_json::from_value(seed).unwrap());
+}
+
+fn demo_seed() -> Value {
+    json!({
+        "history": [
+            { "champion": "Paper", "owner_id": "alice.near", "block_height": START_BLOCK + 10 },
+            { "champion": "scissors", "owner_id": "bob.near", "block_height": START_BLOCK + 20 },
+        ],
+        "player_stats": [
+            ["alice.near", { "attempts": 4, "wins": 1, "last_active_block": START_BLOCK + 10 }],
+            ["bob.near", { "attempts": 2, "wins": 1, "last_active_block": START_BLOCK + 20 }],
+        ],
+        "config": { "min_champion_len": 3 },
+    })
+}
+
+/// Pauses a game at block `START_BLOCK + 30`, ready to be seeded.
+fn paused_game() -> Contract {
+    let mut contract = new_game();
+    contract.set_paused(true);
+    advance_blocks(30);
+    contract
+}
+
+#[test]
+fn seeded_state_reads_back() {
+    let mut contract = paused_game();
+
+    seed(&mut contract, demo_seed());
+
+    assert_eq!(contract.get_champion(), "scissors");
+    assert_eq!(contract.get_champion_owner(), bob());
+    let profile = contract.get_current_champion_profile();
+    assert_eq!(profile.since_block, START_BLOCK + 20);
+    let changes: Vec<String> = contract
+        .get_champion_changes(START_BLOCK + 1, START_BLOCK + 30, None)
+        .into_iter()
+        .map(|record| record.champion)
+        .collect();
+    assert_eq!(changes, vec!["paper", "scissors"]);
+    assert_eq!(contract.get_player_stats(alice()).unwrap().attempts, 4);
+    assert_eq!(contract.get_min_champion_len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "ERR_ALREADY_SEEDED")]
+fn game_is_seeded_once() {
+    let mut contract = paused_game();
+    seed(&mut contract, demo_seed());
+
+    seed(&mut contract, demo_seed());
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_PAUSED")]
+fn seeding_requires_a_pause() {
+    let mut contract = new_game();
+
+    seed(&mut contract, demo_seed());
+}
+
+#[test]
+#[should_panic(expected = "ERR_BAD_PLAYER_STATS")]
+fn player_stats_are_validated() {
+    let mut contract = paused_game();
+    let mut data = demo_seed();
+    data["player_stats"][0][1]["wins"] = 5.into();
+
+    seed(&mut contract, data);
+}
+
+#[test]
+#[should_panic(expected = "ERR_RECORD_OUT_OF_ORDER")]
+fn history_must_be_in_block_order() {
+    let mut contract = paused_game();
+    let mut data = demo_seed();
+    data["history"][1]["block_height"] = (START_BLOCK + 5).into();
+
+    seed(&mut contract, data);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::NearToken;
+
+fn game_with_champions(champions: &[&str]) -> Contract {
+    set_context(&owner(), NearToken::from_yoctonear(0), START_BLOCK);
+    Contract::new(
+        owner(),
+        operator(),
+        INITIAL_CHAMPION.to_string(),
+        None,
+        Some(champions.iter().map(|word| word.to_string()).collect()),
+        "agent".to_string(),
+        "Decide whether the guess beats the champion.".to_string(),
+        agent_public_key(),
+        None,
+    )
+}
+
+#[test]
+fn close_word_returns_the_nearest_champion() {
+    let contract = game_with_champions(&["paper", "scissors"]);
+
+    assert_eq!(
+        contract.is_similar_to_past_champion("Papers".to_string(), 2),
+        Some("paper".to_string())
+    );
+    assert_eq!(
+        contract.is_similar_to_past_champion("rocks".to_string(), 1),
+        Some("rock".to_string())
+    );
+}
+
+#[test]
+fn distant_word_has_no_match() {
+    let contract = game_with_champions(&["paper", "scissors"]);
+
+    assert_eq!(
+        contract.is_similar_to_past_champion("lightning".to_string(), 2),
+        None
+    );
+}
+
+#[test]
+fn exact_match_needs_no_distance() {
+    let contract = game_with_champions(&["paper"]);
+
+    assert_eq!(
+        contract.is_similar_to_past_champion("paper".to_string(), 0),
+        Some("paper".to_string())
+    );
+    assert_eq!(
+        contract.is_similar_to_past_champion("pager".to_string(), 0),
+        None
+    );
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use common::*;
+
+#[test]
+fn difficulty_is_the_recent_win_share() {
+    let mut contract = new_game();
+    assert_eq!(contract.get_difficulty(), 0);
+
+    for word in ["feather", "cloud", "smoke"] {
+        let request_id = guess(&mut contract, &alice(), word);
+        judge(&mut contract, request_id, false);
+    }
+    let request_id = guess(&mut contract, &bob(), "paper");
+    judge(&mut contract, request_id, true);
+
+    assert_eq!(contract.get_difficulty(), 2_500);
+}
+
+#[test]
+fn difficulty_only_looks_at_the_recent_window() {
+    let mut contract = new_game();
+    contract.set_known_answer("paper".to_string(), true);
+    contract.set_known_answer("feather".to_string(), false);
+    guess(&mut contract, &alice(), "paper");
+
+    for _ in 0..99 {
+        guess(&mut contract, &bob(), "feather");
+    }
+    assert_eq!(contract.get_difficulty(), 100);
+
+    guess(&mut contract, &bob(), "feather");
+    assert_eq!(contract.get_difficulty(), 0);
+}
+
+/// Account order of the leaderboard page sorted by `sort_by`.
+fn leaderboard(
+    contract: &contract::Contract,
+    from_index: Option<u64>,
+    limit: Option<u64>,
+    sort_by: &str,
+) -> Vec<String> {
+    let sort_by = near_sdk::serde_json::from_value(sort_by.into()).unwrap();
+    contract
+        .get_leaderboard(from_index, limit, sort_by)
+        .into_iter()
+        .map(|(account_id, _)| account_id.to_string())
+        .collect()
+}
+
+#[test]
+fn leaderboard_sorts_by_wins_or_attempts() {
+    let mut contract = new_game();
+    for (account, word, guess_wins) in [
+        (alice(), "paper", true),
+        (bob(), "feather", false),
+        (bob(), "cloud", false),
+        (bob(), "smoke", false),
+        (carol(), "scissors", true),
+        (carol(), "stone", false),
+    ] {
+        let request_id = guess(&mut contract, &account, word);
+        judge(&mut contract, request_id, guess_wins);
+    }
+
+    assert_eq!(
+        leaderboard(&contract, None, None, "Wins"),
+        vec!["carol.near", "alice.near", "bob.near"]
+    );
+    assert_eq!(
+        leaderboard(&contract, None, None, "Attempts"),
+        vec!["bob.near", "carol.near", "alice.near"]
+    );
+    assert_eq!(
+        leaderboard(&contract, Some(1), Some(1), "Wins"),
+        vec!["alice.near"]
+    );
+}
+
+#[test]
+fn pruning_only_drops_inactive_players() {
+    let mut contract = new_game();
+    for (account, word) in [(alice(), "feather"), (carol(), "cloud")] {
+        let request_id = guess(&mut contract, &account, word);
+        judge(&mut contract, request_id, false);
+    }
+    advance_blocks(500);
+    let request_id = guess(&mut contract, &bob(), "smoke");
+    judge(&mut contract, request_id, false);
+    advance_blocks(100);
+
+    call_as(&owner());
+    assert_eq!(contract.prune_inactive_players(200, 1), 1);
+    assert_eq!(contract.prune_inactive_players(200, 10), 1);
+    assert_eq!(contract.prune_inactive_players(200, 10), 0);
+
+    assert!(contract.get_player_stats(alice()).is_none());
+    assert!(contract.get_player_stats(carol()).is_none());
+    assert_eq!(contract.get_player_stats(bob()).unwrap().attempts, 1);
+}
+
+#[test]
+fn champion_win_rate_counts_every_ruling_against_the_word() {
+    let mut contract = new_game();
+    for (word, guess_wins) in [
+        ("feather", false),
+        ("cloud", false),
+        ("smoke", false),
+        ("paper", true),
+    ] {
+        let request_id = guess(&mut contract, &alice(), word);
+        judge(&mut contract, request_id, guess_wins);
+    }
+    call_as(&owner());
+    contract.set_known_answer("glue".to_string(), false);
+    guess(&mut contract, &bob(), "glue");
+
+    assert_eq!(
+        contract.get_champion_win_rate("Rock".to_string()),
+        Some(2_500)
+    );
+    assert_eq!(contract.get_champion_win_rate("paper".to_string()), Some(0));
+    assert_eq!(contract.get_champion_win_rate("glue".to_string()), None);
+}
+
+#[test]
+fn known_answers_count_in_player_stats() {
+    let mut contract = new_game();
+    contract.set_known_answer("paper".to_string(), true);
+    contract.set_known_answer("feather".to_string(), false);
+
+    guess(&mut contract, &alice(), "feather");
+    guess(&mut contract, &alice(), "paper");
+
+    let stats = contract.get_player_stats(alice()).unwrap();
+    assert_eq!((stats.attempts, stats.wins), (2, 1));
+}
+
+#[test]
+fn challengers_are_listed_per_champion_once() {
+    let mut contract = new_game();
+    for (account, word) in [(bob(), "feather"), (alice(), "cloud"), (bob(), "smoke")] {
+        let request_id = guess(&mut contract, &account, word);
+        judge(&mut contract, request_id, false);
+    }
+    let request_id = guess(&mut contract, &carol(), "paper");
+    judge(&mut contract, request_id, true);
+    guess(&mut contract, &alice(), "scissors");
+
+    assert_eq!(
+        contract.get_challengers("Rock".to_string(), None, None),
+        vec![bob(), alice(), carol()]
+    );
+    assert_eq!(
+        contract.get_challengers("rock".to_string(), Some(1), Some(1)),
+        vec![alice()]
+    );
+    assert_eq!(
+        contract.get_challengers("paper".to_string(), None, None),
+        vec![alice()]
+    );
+    assert!(contract
+        .get_challengers("stone".to_string(), None, None)
+        .is_empty());
+}
+
+#[test]
+fn latency_histogram_buckets_blocks_until_respond() {
+    let mut contract = new_game();
+    for (word, latency) in [("feather", 0), ("cloud", 5), ("smoke", 6), ("glue", 150)] {
+        let request_id = guess(&mut contract, &alice(), word);
+        advance_blocks(latency);
+        judge(&mut contract, request_id, false);
+    }
+    let timed_out = guess(&mut contract, &bob(), "water");
+    advance_blocks(201);
+    contract.force_timeout(timed_out);
+
+    assert_eq!(
+        contract.get_latency_histogram(),
+        vec![
+            (5, 2),
+            (10, 1),
+            (20, 0),
+            (50, 0),
+            (100, 0),
+            (200, 1),
+            (u64::MAX, 0),
+        ]
+    );
+}
+
+#[test]
+fn outcome_counts_cover_wins_and_losses() {
+    let mut contract = new_game();
+    for (word, guess_wins) in [("feather", false), ("paper", true), ("cloud", false)] {
+        let request_id = guess(&mut contract, &alice(), word);
+        judge(&mut contract, request_id, guess_wins);
+    }
+    let timed_out = guess(&mut contract, &bob(), "water");
+    advance_blocks(201);
+    contract.force_timeout(timed_out);
+
+    let counts = contract.get_outcome_counts();
+    assert_eq!(counts.wins, 1);
+    assert_eq!(counts.losses, 2);
+}
+
+#[test]
+fn dominance_score_weighs_reign_hold_rate_and_defeats() {
+    let mut contract = new_game();
+    advance_blocks(5);
+    assert_eq!(contract.get_dominance_score(), 5);
+
+    for word in ["feather", "cloud", "smoke"] {
+        let request_id = guess(&mut contract, &alice(), word);
+        judge(&mut contract, request_id, false);
+    }
+    advance_blocks(35);
+    call_as(&owner());
+    contract.set_dominance_weights(
+        near_sdk::serde_json::from_value(near_sdk::serde_json::json!({
+            "reign_block": 2,
+            "hold_rate_bps": 1,
+            "challenger_defeated": 100,
+        }))
+        .unwrap(),
+    );
+
+    assert_eq!(contract.get_dominance_score(), 40 * 2 + 10_000 + 3 * 100);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::{serde_json, NearToken};
+
+fn game_with_storage_staking() -> Contract {
+    let mut contract = new_game();
+    let mut flags = serde_json::to_value(contract.get_feature_flags()).unwrap();
+    flags["storage_staking"] = true.into();
+    contract.set_feature_flags(serde_json::from_value(flags).unwrap());
+    contract
+}
+
+/// Opens a 1 NEAR request from alice and returns its id and storage stake.
+fn staked_guess(contract: &mut Contract) -> (u64, NearToken) {
+    let request_id = guess_with(contract, &alice(), "paper", near(1));
+    let stake = contract.get_storage_stake(request_id);
+    assert!(!stake.is_zero());
+    assert_eq!(
+        contract.get_escrow(request_id),
+        near(1).saturating_sub(stake)
+    );
+    (request_id, stake)
+}
+
+/// Checks the last call sent alice `escrow_refund` plus a storage refund of
+/// at most `stake`.
+fn assert_storage_refunded(escrow_refund: NearToken, stake: NearToken) {
+    let storage_refund = transferred_to(&alice()).saturating_sub(escrow_refund);
+    assert!(!storage_refund.is_zero());
+    assert!(storage_refund <= stake);
+}
+
+#[test]
+fn resolution_refunds_the_storage() {
+    let mut contract = game_with_storage_staking();
+    let (request_id, stake) = staked_guess(&mut contract);
+
+    judge(&mut contract, request_id, false);
+
+    assert_storage_refunded(NearToken::from_yoctonear(0), stake);
+    assert!(contract.get_storage_stake(request_id).is_zero());
+}
+
+#[test]
+fn removal_refunds_the_storage() {
+    let mut contract = game_with_storage_staking();
+    let (request_id, stake) = staked_guess(&mut contract);
+    let escrow = contract.get_escrow(request_id);
+
+    call_as(&operator());
+    contract.remove_request(request_id);
+
+    assert_storage_refunded(escrow, stake);
+}
+
+#[test]
+fn cancellation_refunds_the_storage() {
+    let mut contract = game_with_storage_staking();
+    let (request_id, stake) = staked_guess(&mut contract);
+    let escrow = contract.get_escrow(request_id);
+
+    call_as(&alice());
+    contract.cancel_my_requests(1);
+
+    assert_storage_refunded(escrow, stake);
+}
+
+#[test]
+fn timeout_refunds_the_storage() {
+    let mut contract = game_with_storage_staking();
+    let (request_id, stake) = staked_guess(&mut contract);
+    let escrow = contract.get_escrow(request_id);
+
+    advance_blocks(201);
+    call_as(&bob());
+    contract.force_timeout(request_id);
+
+    assert_storage_refunded(escrow, stake);
+}
+
+#[test]
+fn nothing_is_staked_without_the_flag() {
+    let mut contract = new_game();
+    let request_id = guess_with(&mut contract, &alice(), "paper", near(1));
+
+    assert!(contract.get_storage_stake(request_id).is_zero());
+    assert_eq!(contract.get_escrow(request_id), near(1));
+}
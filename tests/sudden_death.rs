@@ -0,0 +1,44 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+
+fn game_with_target(target: &str) -> Contract {
+    let mut contract = new_game();
+    contract.set_target_champion(Some(target.to_string()));
+    contract
+}
+
+#[test]
+fn crowning_the_target_ends_the_game() {
+    let mut contract = game_with_target("Paper");
+    assert_eq!(contract.get_target_champion().as_deref(), Some("paper"));
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    judge(&mut contract, request_id, true);
+
+    assert!(contract.is_paused());
+    let game_won = event("game_won");
+    assert_eq!(game_won["data"][0]["champion"], "paper");
+    assert_eq!(game_won["data"][0]["owner_id"], "alice.near");
+}
+
+#[test]
+fn other_champions_keep_the_game_going() {
+    let mut contract = game_with_target("scissors");
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    judge(&mut contract, request_id, true);
+
+    assert!(!contract.is_paused());
+}
+
+#[test]
+#[should_panic(expected = "Contact paused")]
+fn no_requests_after_the_game_ended() {
+    let mut contract = game_with_target("paper");
+    let request_id = guess(&mut contract, &alice(), "paper");
+    judge(&mut contract, request_id, true);
+
+    guess(&mut contract, &bob(), "scissors");
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::json;
+
+fn tagged_guess(contract: &mut Contract, tags: &[&str]) -> u64 {
+    call_as(&alice());
+    let request_id = contract.get_next_request_id();
+    let tags = tags.iter().map(|tag| tag.to_string()).collect();
+    contract.request("paper".to_string(), None, None, None, Some(tags));
+    request_id
+}
+
+#[test]
+fn tags_are_stored_lowercase_and_announced() {
+    let mut contract = new_game();
+    let request_id = tagged_guess(&mut contract, &["Object", "flat"]);
+
+    assert_eq!(
+        request_json(&contract, request_id)["tags"],
+        json!(["object", "flat"])
+    );
+    assert_eq!(
+        event("request_created")["data"][0]["tags"],
+        json!(["object", "flat"])
+    );
+}
+
+#[test]
+fn tags_are_optional() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    assert_eq!(request_json(&contract, request_id)["tags"], json!([]));
+}
+
+#[test]
+#[should_panic(expected = "ERR_TOO_MANY_TAGS")]
+fn tag_count_is_limited() {
+    let mut contract = new_game();
+    tagged_guess(&mut contract, &["a", "b", "c", "d", "e", "f"]);
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_TAG")]
+fn tag_length_is_limited() {
+    let mut contract = new_game();
+    tagged_guess(&mut contract, &[&"a".repeat(33)]);
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_TAG")]
+fn empty_tags_are_rejected() {
+    let mut contract = new_game();
+    tagged_guess(&mut contract, &[""]);
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use common::*;
+use contract::Contract;
+use near_sdk::serde_json::{self, json, Value};
+use near_sdk::NearToken;
+
+fn set_tie_break(contract: &mut Contract, tie_break: &str) {
+    call_as(&owner());
+    contract.set_tie_break(serde_json::from_value(json!(tie_break)).unwrap());
+}
+
+fn outcome_of(contract: &Contract, request_id: u64) -> Value {
+    let entry = contract
+        .get_responses_log(None, None)
+        .into_iter()
+        .find(|entry| entry.request_id == request_id)
+        .unwrap();
+    serde_json::to_value(&entry.outcome).unwrap()
+}
+
+/// Alice and Bob both beat the champion, and both rulings are submitted
+/// before either resolves, all within one block.
+fn race(contract: &mut Contract, alice_deposit: NearToken, bob_deposit: NearToken) -> (u64, u64) {
+    let first = guess_with(contract, &alice(), "paper", alice_deposit);
+    let second = guess_with(contract, &bob(), "water", bob_deposit);
+
+    let response = ruling(contract, first, true);
+    respond(contract, first, response);
+    let response = ruling(contract, second, true);
+    respond(contract, second, response);
+    (first, second)
+}
+
+#[test]
+fn first_resolved_ruling_wins_by_default() {
+    let mut contract = new_game();
+    let (first, second) = race(&mut contract, near(1), near(1));
+
+    resolve(&mut contract, second);
+    resolve(&mut contract, first);
+
+    assert_eq!(contract.get_current_champion_profile().word, "water");
+    assert_eq!(outcome_of(&contract, second), "Won");
+    assert_eq!(outcome_of(&contract, first), "Superseded");
+}
+
+#[test]
+fn lowest_request_id_wins_whatever_resolves_first() {
+    let mut contract = new_game();
+    set_tie_break(&mut contract, "LowestRequestId");
+    let (first, second) = race(&mut contract, near(1), near(1));
+
+    resolve(&mut contract, second);
+    assert_eq!(contract.get_current_champion_profile().word, "rock");
+    resolve(&mut contract, first);
+
+    assert_eq!(contract.get_current_champion_profile().word, "paper");
+    assert_eq!(outcome_of(&contract, first), "Won");
+    assert_eq!(outcome_of(&contract, second), "Superseded");
+}
+
+#[test]
+fn highest_deposit_wins_and_the_loser_is_partially_refunded() {
+    let mut contract = new_game();
+    contract.set_win_fee_bps(1_000);
+    set_tie_break(&mut contract, "HighestDeposit");
+    let (first, second) = race(&mut contract, near(1), near(2));
+
+    resolve(&mut contract, first);
+    assert_eq!(transferred_to(&alice()), NearToken::from_millinear(900));
+    resolve(&mut contract, second);
+
+    assert_eq!(contract.get_current_champion_profile().word, "water");
+    assert_eq!(outcome_of(&contract, first), "Superseded");
+    assert_eq!(outcome_of(&contract, second), "Won");
+}
+
+#[test]
+fn equal_deposits_fall_back_to_the_lowest_request_id() {
+    let mut contract = new_game();
+    set_tie_break(&mut contract, "HighestDeposit");
+    let (first, second) = race(&mut contract, near(1), near(1));
+
+    resolve(&mut contract, second);
+    resolve(&mut contract, first);
+
+    assert_eq!(contract.get_current_champion_profile().word, "paper");
+}
+
+#[test]
+fn pending_loss_does_not_outrank_a_win() {
+    let mut contract = new_game();
+    set_tie_break(&mut contract, "LowestRequestId");
+    let first = guess(&mut contract, &alice(), "feather");
+    let second = guess(&mut contract, &bob(), "paper");
+
+    let response = ruling(&contract, first, false);
+    respond(&mut contract, first, response);
+    let response = ruling(&contract, second, true);
+    respond(&mut contract, second, response);
+    resolve(&mut contract, second);
+
+    assert_eq!(contract.get_current_champion_profile().word, "paper");
+    assert_eq!(outcome_of(&contract, second), "Won");
+}
+
+#[test]
+fn tie_break_is_part_of_the_config() {
+    let mut contract = new_game();
+    let hash = contract.get_config_hash();
+
+    call_as(&owner());
+    contract
+        .update_config(serde_json::from_value(json!({ "tie_break": "HighestDeposit" })).unwrap());
+
+    assert_eq!(
+        serde_json::to_value(contract.get_tie_break()).unwrap(),
+        "HighestDeposit"
+    );
+    assert!(contract
+        .get_config_kv()
+        .contains(&("tie_break".to_string(), "\"HighestDeposit\"".to_string())));
+    assert_ne!(contract.get_config_hash(), hash);
+}
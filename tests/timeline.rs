@@ -0,0 +1,69 @@
+mod common;
+
+use common::*;
+
+#[test]
+fn timeline_records_each_stage_of_a_request() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+    let timeline = contract.get_request_timeline(request_id).unwrap();
+    assert_eq!(timeline.created, START_BLOCK);
+    assert_eq!(timeline.agent_notified, START_BLOCK);
+    assert!(timeline.request_gas_used.is_some());
+    assert!(timeline.responded.is_none() && timeline.resolved.is_none());
+
+    advance_blocks(3);
+    call_as(&operator());
+    contract.ack_request(request_id);
+    advance_blocks(4);
+    call_as(&owner());
+    contract.reassign_requests(10, None);
+    advance_blocks(5);
+    let response = ruling(&contract, request_id, true);
+    respond(&mut contract, request_id, response);
+    advance_blocks(1);
+    resolve(&mut contract, request_id);
+
+    let timeline = contract.get_request_timeline(request_id).unwrap();
+    assert_eq!(timeline.created, START_BLOCK);
+    assert_eq!(timeline.acked, Some(START_BLOCK + 3));
+    assert_eq!(timeline.agent_notified, START_BLOCK + 7);
+    assert_eq!(timeline.responded, Some(START_BLOCK + 12));
+    assert_eq!(timeline.resolved, Some(START_BLOCK + 13));
+}
+
+#[test]
+fn timed_out_request_is_resolved_without_a_response() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    advance_blocks(201);
+    call_as(&bob());
+    contract.force_timeout(request_id);
+
+    let timeline = contract.get_request_timeline(request_id).unwrap();
+    assert!(timeline.responded.is_none());
+    assert_eq!(timeline.resolved, Some(START_BLOCK + 201));
+}
+
+#[test]
+fn unknown_requests_have_no_timeline() {
+    let contract = new_game();
+
+    assert!(contract.get_request_timeline(0).is_none());
+}
+
+#[test]
+fn respond_records_the_metrics_before_the_callback() {
+    let mut contract = new_game();
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    advance_blocks(7);
+    let response = ruling(&contract, request_id, true);
+    respond(&mut contract, request_id, response);
+
+    let timeline = contract.get_request_timeline(request_id).unwrap();
+    assert_eq!(timeline.responded, Some(START_BLOCK + 7));
+    assert!(timeline.resolved.is_none());
+    assert_eq!(contract.get_latency_histogram()[1], (10, 1));
+}
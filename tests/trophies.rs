@@ -0,0 +1,127 @@
+mod common;
+
+use common::*;
+use near_sdk::mock::MockAction;
+use near_sdk::serde_json::{self, Value};
+use near_sdk::test_utils::get_created_receipts;
+use near_sdk::{AccountId, Gas, NearToken};
+
+fn nft() -> AccountId {
+    "trophies.near".parse().unwrap()
+}
+
+fn enable_trophies(contract: &mut contract::Contract) {
+    call_as(&owner());
+    contract.set_trophy_config(Some(nft()), NearToken::from_millinear(10));
+}
+
+/// Arguments of every `nft_mint` the last call made.
+fn minted_trophies() -> Vec<Value> {
+    get_created_receipts()
+        .iter()
+        .filter(|receipt| receipt.receiver_id == nft())
+        .flat_map(|receipt| receipt.actions.iter())
+        .filter_map(|action| match action {
+            MockAction::FunctionCallWeight {
+                method_name, args, ..
+            } if method_name == b"nft_mint" => Some(serde_json::from_slice(args).unwrap()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn enabling_trophies_reserves_the_mint_gas() {
+    let mut contract = new_game();
+    let min_prepaid_gas = contract.get_min_prepaid_gas();
+
+    call_as(&owner());
+    contract.set_trophy_config(Some(nft()), NearToken::from_millinear(10));
+
+    assert_eq!(
+        contract.get_min_prepaid_gas(),
+        min_prepaid_gas.saturating_add(Gas::from_tgas(15))
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_PREPAID_GAS_TOO_LOW")]
+fn prepaid_gas_cannot_drop_below_the_mint_gas() {
+    let mut contract = new_game();
+    let min_prepaid_gas = contract.get_min_prepaid_gas();
+
+    call_as(&owner());
+    contract.set_trophy_config(Some(nft()), NearToken::from_millinear(10));
+    contract.set_min_prepaid_gas(min_prepaid_gas);
+}
+
+#[test]
+fn winning_mints_a_trophy_for_the_new_champion() {
+    let mut contract = new_game();
+    enable_trophies(&mut contract);
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    judge(&mut contract, request_id, true);
+
+    let trophies = minted_trophies();
+    assert_eq!(trophies.len(), 1);
+    assert_eq!(trophies[0]["token_id"], "0");
+    assert_eq!(trophies[0]["token_owner_id"], alice().as_str());
+    assert_eq!(trophies[0]["token_metadata"]["title"], "paper");
+}
+
+#[test]
+fn losing_mints_nothing() {
+    let mut contract = new_game();
+    enable_trophies(&mut contract);
+    let request_id = guess(&mut contract, &alice(), "paper");
+
+    judge(&mut contract, request_id, false);
+
+    assert!(minted_trophies().is_empty());
+}
+
+#[test]
+fn known_answer_wins_mint_trophies() {
+    let mut contract = new_game();
+    enable_trophies(&mut contract);
+    contract.set_known_answer("paper".to_string(), true);
+    contract.set_known_answer("scissors".to_string(), true);
+
+    guess(&mut contract, &alice(), "paper");
+    assert_eq!(minted_trophies()[0]["token_id"], "0");
+    guess(&mut contract, &bob(), "scissors");
+
+    let trophies = minted_trophies();
+    assert_eq!(trophies.len(), 1);
+    assert_eq!(trophies[0]["token_id"], "1");
+    assert_eq!(trophies[0]["token_owner_id"], bob().as_str());
+    assert_eq!(trophies[0]["token_metadata"]["title"], "scissors");
+    assert_eq!(trophies[0]["token_metadata"]["description"], "known answer");
+}
+
+#[test]
+fn recrowning_the_reigning_champion_mints_nothing() {
+    let mut contract = new_game();
+    enable_trophies(&mut contract);
+    contract.set_known_answer("paper".to_string(), true);
+    guess(&mut contract, &alice(), "paper");
+
+    guess(&mut contract, &alice(), "paper");
+
+    assert!(minted_trophies().is_empty());
+    assert_eq!(contract.get_champion_owner(), alice());
+}
+
+#[test]
+fn same_word_for_a_new_owner_is_a_new_reign() {
+    let mut contract = new_game();
+    enable_trophies(&mut contract);
+    contract.set_known_answer("paper".to_string(), true);
+    guess(&mut contract, &alice(), "paper");
+
+    guess(&mut contract, &bob(), "paper");
+
+    assert_eq!(minted_trophies()[0]["token_id"], "1");
+    assert_eq!(contract.get_champion_owner(), bob());
+}